@@ -1,9 +1,3 @@
-#![feature(const_fn)]
-#![feature(const_panic)]
-#![feature(const_if_match)]
-#![feature(const_generics)]
-#![feature(const_loop)]
-#![allow(incomplete_features)]
 #![allow(unused_imports)]
 #![allow(dead_code)]
 
@@ -11,8 +5,8 @@
 extern crate merx;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use merx::{asset::CheckedOps, Asset, Credit, Debt};
+use std::convert::TryFrom;
 
-get_traits!();
 new_asset!(bench_asset_64, 1, 2147483647000);
 new_asset!(bench_asset_32, 1, 1000000);
 new_asset!(bench_asset_128, 10, 2147483647000);
@@ -25,7 +19,7 @@ fn checked_add_and_compare_64(a: i64, b: i64, max: i64) -> Option<i64> {
     if sum.checked_abs()? <= max {
         return Some(sum);
     }
-    return None;
+    None
 }
 
 fn checked_mul_and_compare_64(a: i64, b: i64, max: i64) -> Option<i64> {
@@ -33,7 +27,7 @@ fn checked_mul_and_compare_64(a: i64, b: i64, max: i64) -> Option<i64> {
     if res.checked_abs()? <= max {
         return Some(res);
     }
-    return None;
+    None
 }
 
 fn checked_div_and_compare_64(a: i64, b: i64, max: i64) -> Option<i64> {
@@ -41,14 +35,14 @@ fn checked_div_and_compare_64(a: i64, b: i64, max: i64) -> Option<i64> {
     if res.checked_abs()? <= max {
         return Some(res);
     }
-    return None;
+    None
 }
 fn checked_add_and_compare_32(a: i32, b: i32, max: i32) -> Option<i32> {
     let sum = a.checked_add(b)?;
     if sum.checked_abs()? <= max {
         return Some(sum);
     }
-    return None;
+    None
 }
 
 fn checked_mul_and_compare_32(a: i32, b: i32, max: i32) -> Option<i32> {
@@ -56,7 +50,7 @@ fn checked_mul_and_compare_32(a: i32, b: i32, max: i32) -> Option<i32> {
     if res.checked_abs()? <= max {
         return Some(res);
     }
-    return None;
+    None
 }
 
 fn checked_div_and_compare_32(a: i32, b: i32, max: i32) -> Option<i32> {
@@ -64,14 +58,14 @@ fn checked_div_and_compare_32(a: i32, b: i32, max: i32) -> Option<i32> {
     if res.checked_abs()? <= max {
         return Some(res);
     }
-    return None;
+    None
 }
 fn checked_add_and_compare_128(a: i128, b: i128, max: i128) -> Option<i128> {
     let sum = a.checked_add(b)?;
     if sum.checked_abs()? <= max {
         return Some(sum);
     }
-    return None;
+    None
 }
 
 fn checked_mul_and_compare_128(a: i128, b: i128, max: i128) -> Option<i128> {
@@ -79,7 +73,7 @@ fn checked_mul_and_compare_128(a: i128, b: i128, max: i128) -> Option<i128> {
     if res.checked_abs()? <= max {
         return Some(res);
     }
-    return None;
+    None
 }
 
 fn checked_div_and_compare_128(a: i128, b: i128, max: i128) -> Option<i128> {
@@ -87,7 +81,7 @@ fn checked_div_and_compare_128(a: i128, b: i128, max: i128) -> Option<i128> {
     if res.checked_abs()? <= max {
         return Some(res);
     }
-    return None;
+    None
 }
 
 pub fn add_64b_int(c: &mut Criterion) {
@@ -1,20 +1,21 @@
-#![feature(const_fn)]
-#![feature(const_panic)]
-#![feature(const_generics)]
-#![allow(incomplete_features)]
 #![allow(unused_imports)]
 #![allow(dead_code)]
 
 #[macro_use]
 extern crate merx;
-use merx::{asset::CheckedOps, Asset, Credit, Debt};
-
-get_traits!();
+use merx::{
+    asset::{CheckedOps, Rate, RoundAt},
+    Asset, Credit, Debt, Error, RoundingMode,
+};
+use std::convert::TryFrom;
 
 // Create a new asset called bitcoin with 8 decimal digits and a max value of 21 million of units
 new_asset!(bitcoin, 8, 21_000_000);
 // Create a new asset called usd with 2 decimal digits and a max value of 14_000_000_000_000 units
 new_asset!(usd, 4, 14_000_000_000_000);
+// Create a new asset called points with 2 decimal digits and no upper bound, the only limit is the
+// range of i128: `new_asset!(points, 2, unbounded)` is the same
+new_asset!(points, 2);
 
 type Bitcoin = Asset<bitcoin::Value>;
 type Usd = Asset<usd::Value>;
@@ -27,43 +28,67 @@ fn main() {
     let to_pay = Bitcoin::try_from(-29).unwrap();
     let remain = (tot_amount + to_pay).unwrap();
     println!("{:#?}", remain);
+    // Subtraction and negation are checked too, the absolute value is always a Credit
+    let owed = (to_pay - tot_amount).unwrap();
+    println!("{}", owed.abs().unwrap());
 
     // With a float also a rounding method must be provided, this because Merx must know what to do
-    // with floats with higher precision than the asset. Possible rounding methods are Trunc Floor
-    // Ceil Round and they behaved the same as rust's f64 methods with the same names.
-    let usd = Usd::try_from((10.87, FloatRounding::Trunc)).unwrap();
+    // with floats with higher precision than the asset. The same `RoundingMode` is used by every
+    // lossy operation: HalfEven (banker's rounding), HalfUp, HalfDown, HalfTowardZero,
+    // HalfAwayFromZero, AwayFromZero, TowardZero, Floor, Ceil and Unnecessary that fails if the
+    // value can not be represented exactly.
+    let usd = Usd::try_from((10.87, RoundingMode::HalfEven)).unwrap();
     println!("{:#?}", usd);
 
     // When the source of the float is a text string the best thing to do is to parse the value
-    // from a string.
+    // from a string. Signs, `_` separators and exponents are accepted ("-1_000.5", "1.5e-3") and
+    // an invalid string is an `Error::Parse` with the byte offset of the problem.
     let usd = Usd::try_from("10.87").unwrap();
     println!("{:#?}", usd);
+    let usd: Usd = "-1_000.5".parse().unwrap();
+    println!("{:#?}", usd);
+    // Display prints the amount padded to the precision of the asset and can be parsed back.
+    println!("{}", usd);
+
+    // Assets are converted with an exchange rate, the result is rounded once to the precision of
+    // the target asset and checked against its bound. A Debt stays a Debt and a Credit a Credit.
+    let rate = Rate::<bitcoin::Value, usd::Value>::try_from("6543.21").unwrap();
+    let usd: Usd = remain
+        .convert(rate, RoundingMode::HalfAwayFromZero)
+        .unwrap();
+    println!("{}", usd);
 
-    // TODO smouthly conversion
-    //let x: USD = match remain {
-    //    Credit(x) => interests(USD::from(x), 12, 3);
-    //    Debt(x) => interests(USD::from(x), 12, 3);
-    //};
+    // Interests: 3% per period for 12 periods, compounded every period and rounded at the end.
+    // The rate is a (mantissa, decimal part) tuple like the amounts.
+    let interest = usd
+        .compound_interest((3, 2), 12, RoundingMode::HalfAwayFromZero, RoundAt::End)
+        .unwrap();
+    println!("{}", interest);
 }
 
 // You can define function over generic assets:
 
 // Adding assets of type T return an asset of type T
-fn add_assets<T: CheckedOps>(x: Asset<T>, y: Asset<T>) -> Option<Asset<T>> {
+fn add_assets<T: CheckedOps>(x: Asset<T>, y: Asset<T>) -> Result<Asset<T>, Error> {
     x + y
 }
 
 // Adding credits can only result in a Credit
-fn add_credits<T: CheckedOps>(x: Credit<T>, y: Credit<T>) -> Option<Credit<T>> {
+fn add_credits<T: CheckedOps>(x: Credit<T>, y: Credit<T>) -> Result<Credit<T>, Error> {
     x + y
 }
 
 // Adding debts can only result in a Debt
-fn add_debts<T: CheckedOps>(x: Debt<T>, y: Debt<T>) -> Option<Debt<T>> {
+fn add_debts<T: CheckedOps>(x: Debt<T>, y: Debt<T>) -> Result<Debt<T>, Error> {
     x + y
 }
 
+// Paying off a debt can only result in a Credit
+fn pay_debt<T: CheckedOps>(x: Credit<T>, y: Debt<T>) -> Result<Credit<T>, Error> {
+    x - y
+}
+
 // Adding debts can only result in a Debt
-fn add_debts2<T: CheckedOps>(x: Debt<T>, y: Debt<T>) -> Option<Debt<T>> {
+fn add_debts2<T: CheckedOps>(x: Debt<T>, y: Debt<T>) -> Result<Debt<T>, Error> {
     x + y
 }
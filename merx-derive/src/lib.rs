@@ -0,0 +1,355 @@
+//! Derive macro to define merx assets, use it through `merx` with the `derive` feature.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, ExprUnary, Ident, Lit};
+use syn::{LitInt, LitStr, UnOp};
+
+const ROUNDING_MODES: &[&str] = &[
+    "HalfEven",
+    "HalfUp",
+    "HalfDown",
+    "HalfTowardZero",
+    "HalfAwayFromZero",
+    "AwayFromZero",
+    "TowardZero",
+    "Floor",
+    "Ceil",
+    "Unnecessary",
+];
+
+/// Define an asset on a unit struct, the same as `new_asset!` with some metadata more:
+///
+/// ```
+/// use merx::asset::AssetInfo;
+/// use merx::Asset;
+///
+/// #[derive(merx::Asset)]
+/// #[asset(decimals = 2, max = "14e12", code = "USD", symbol = "$", rounding = HalfEven)]
+/// pub struct Usd;
+///
+/// # fn main() {
+/// let usd: Asset<usd::Value> = "10.50".parse().unwrap();
+/// assert_eq!(usd.to_string(), "10.50");
+/// assert_eq!(usd::Value::CODE, Some("USD"));
+/// # }
+/// ```
+///
+/// The value of the asset is defined in a module named as the struct in snake case (`usd`, a run of
+/// capitals is one word so `USDCoin` is `usd_coin`) with the visibility of the struct, `module =
+/// "name"` sets another name. The attributes are:
+///
+/// * `decimals = 2`: the number of decimal digits, at most 38, required
+/// * `max = 1_000` or `max = "14e12"`: the upper bound in units of the asset, if it is missing
+///   the asset is unbounded
+/// * `min = -500` or `min = "-5e2"`: the lower bound, if it is missing it is `-max`
+/// * `non_negative`: the lower bound is 0 and the value implements `NonNegative`
+/// * `tick = 5`: the minimum unit is `tick * 10^-decimals`
+/// * `code = "USD"`, `symbol = "$"`: the `CODE` and `SYMBOL` of `AssetInfo`
+/// * `rounding = HalfEven`: the `ROUNDING` of `AssetInfo`, `HalfEven` if it is missing
+///
+/// An invalid definition is a compile error that points to the wrong attribute:
+///
+/// ```compile_fail
+/// #[derive(merx::Asset)]
+/// #[asset(decimals = 39, max = 1_000)]
+/// pub struct TooPrecise;
+/// ```
+///
+/// ```compile_fail
+/// #[derive(merx::Asset)]
+/// #[asset(decimals = 2, max = "1e37")]
+/// pub struct TooBig;
+/// ```
+///
+/// ```compile_fail
+/// #[derive(merx::Asset)]
+/// #[asset(decimals = 2, rounding = Round)]
+/// pub struct UnknownRounding;
+/// ```
+#[proc_macro_derive(Asset, attributes(asset))]
+pub fn derive_asset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// An integer attribute with the span of its value
+struct Spanned<T> {
+    value: T,
+    span: Span,
+}
+
+#[derive(Default)]
+struct Definition {
+    decimals: Option<Spanned<u32>>,
+    max: Option<Spanned<i128>>,
+    min: Option<Spanned<i128>>,
+    tick: Option<Spanned<i128>>,
+    non_negative: Option<Span>,
+    code: Option<LitStr>,
+    symbol: Option<LitStr>,
+    rounding: Option<Ident>,
+    module: Option<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    match &input.data {
+        Data::Struct(data) if data.fields.is_empty() => {}
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`Asset` can only be derived for a struct without fields: `pub struct Usd;`",
+            ))
+        }
+    }
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "an asset can not be generic",
+        ));
+    }
+
+    let mut definition = Definition::default();
+    let mut found = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("asset"))
+    {
+        found = true;
+        attr.parse_nested_meta(|meta| definition.parse(meta))?;
+    }
+    if !found {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "missing `#[asset(decimals = ...)]` attribute",
+        ));
+    }
+
+    let decimals = match &definition.decimals {
+        Some(decimals) => decimals,
+        None => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "missing `decimals` in the `#[asset(...)]` attribute",
+            ))
+        }
+    };
+    let pow = 10_i128.pow(decimals.value);
+    let to_units = |bound: &Spanned<i128>, name: &str| {
+        bound.value.checked_mul(pow).ok_or_else(|| {
+            Error::new(
+                bound.span,
+                format!(
+                    "`{}` with {} decimals does not fit in an i128",
+                    name, decimals.value
+                ),
+            )
+        })
+    };
+
+    let max = match &definition.max {
+        Some(max) if max.value < 0 => {
+            return Err(Error::new(max.span, "`max` can not be negative"));
+        }
+        Some(max) => {
+            let max = Literal::i128_suffixed(to_units(max, "max")?);
+            quote!(#max)
+        }
+        None => quote!(::merx::fixed::UNBOUNDED),
+    };
+    let min = match (&definition.min, definition.non_negative) {
+        (Some(min), Some(_)) => {
+            return Err(Error::new(
+                min.span,
+                "`min` can not be set for a `non_negative` asset",
+            ));
+        }
+        (Some(min), None) if min.value > 0 => {
+            return Err(Error::new(min.span, "`min` can not be positive"));
+        }
+        (Some(min), None) => {
+            let min = Literal::i128_suffixed(to_units(min, "min")?);
+            quote!(#min)
+        }
+        (None, Some(_)) => quote!(0_i128),
+        (None, None) if definition.max.is_some() => quote!(-MAX_VAL),
+        (None, None) => quote!(::merx::fixed::UNBOUNDED_MIN),
+    };
+    let tick = match &definition.tick {
+        Some(tick) if tick.value <= 0 => {
+            return Err(Error::new(tick.span, "`tick` must be positive"));
+        }
+        Some(tick) => Literal::i128_unsuffixed(tick.value),
+        None => Literal::i128_unsuffixed(1),
+    };
+    let kind = match definition.non_negative {
+        Some(_) => quote!(non_negative),
+        None => quote!(signed),
+    };
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let module = match &definition.module {
+        Some(module) => module.parse::<Ident>()?,
+        None => Ident::new(&to_snake_case(&ident.to_string()), ident.span()),
+    };
+    let frac = Literal::u32_unsuffixed(decimals.value);
+    let code = definition.code.map(|code| quote!(, code = #code));
+    let symbol = definition.symbol.map(|symbol| quote!(, symbol = #symbol));
+    let rounding = definition
+        .rounding
+        .map(|rounding| quote!(, rounding = #rounding));
+
+    Ok(quote! {
+        ::merx::new_asset!(
+            @impl #vis #module, #frac, #min, #max, #tick, #kind #code #symbol #rounding
+        );
+
+        impl ::merx::asset::Definition for #ident {
+            type Value = #module::Value;
+        }
+    })
+}
+
+impl Definition {
+    fn parse(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
+        let key = match meta.path.get_ident() {
+            Some(key) => key.to_string(),
+            None => return Err(meta.error("expected an asset attribute")),
+        };
+        let duplicated = match key.as_str() {
+            "decimals" => {
+                let lit: LitInt = meta.value()?.parse()?;
+                let value = lit.base10_parse::<u32>()?;
+                if value > 38 {
+                    return Err(Error::new(lit.span(), "`decimals` can be at most 38"));
+                }
+                set(
+                    &mut self.decimals,
+                    Spanned {
+                        value,
+                        span: lit.span(),
+                    },
+                )
+            }
+            "max" => set(&mut self.max, parse_amount(&meta)?),
+            "min" => set(&mut self.min, parse_amount(&meta)?),
+            "tick" => set(&mut self.tick, parse_amount(&meta)?),
+            "non_negative" => set(
+                &mut self.non_negative,
+                meta.path.get_ident().unwrap().span(),
+            ),
+            "code" => {
+                let code: LitStr = meta.value()?.parse()?;
+                let value = code.value();
+                if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(Error::new(code.span(), "`code` must be ASCII alphanumeric"));
+                }
+                set(&mut self.code, code)
+            }
+            "symbol" => set(&mut self.symbol, meta.value()?.parse()?),
+            "rounding" => {
+                let rounding: Ident = meta.value()?.parse()?;
+                if !ROUNDING_MODES.contains(&rounding.to_string().as_str()) {
+                    return Err(Error::new(
+                        rounding.span(),
+                        format!(
+                            "unknown rounding mode, expected one of: {}",
+                            ROUNDING_MODES.join(", ")
+                        ),
+                    ));
+                }
+                set(&mut self.rounding, rounding)
+            }
+            "module" => set(&mut self.module, meta.value()?.parse()?),
+            _ => return Err(meta.error(format!("unknown asset attribute `{}`", key))),
+        };
+        if duplicated {
+            return Err(meta.error(format!("duplicated asset attribute `{}`", key)));
+        }
+        Ok(())
+    }
+}
+
+/// Set `field` and return true if it was already set
+fn set<T>(field: &mut Option<T>, value: T) -> bool {
+    field.replace(value).is_some()
+}
+
+/// Parse an integer amount: `1_000`, `-500`, `"14e12"` or `"-5e2"`
+fn parse_amount(meta: &ParseNestedMeta) -> Result<Spanned<i128>, Error> {
+    let expr: Expr = meta.value()?.parse()?;
+    let (negative, lit) = match &expr {
+        Expr::Lit(ExprLit { lit, .. }) => (false, lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) => (true, lit),
+            _ => return Err(Error::new_spanned(expr, "expected an integer")),
+        },
+        _ => return Err(Error::new_spanned(&expr, "expected an integer")),
+    };
+    let value = match lit {
+        Lit::Int(int) => int.base10_parse::<i128>()?,
+        Lit::Str(string) => parse_integer(&string.value()).ok_or_else(|| {
+            Error::new(
+                string.span(),
+                "expected an integer like \"14e12\" or \"-500\"",
+            )
+        })?,
+        _ => return Err(Error::new_spanned(lit, "expected an integer")),
+    };
+    let value = if negative { -value } else { value };
+    Ok(Spanned {
+        value,
+        span: lit.span(),
+    })
+}
+
+/// Parse an integer with an optional sign, `_` separators and a positive exponent
+fn parse_integer(value: &str) -> Option<i128> {
+    let value = value.replace('_', "");
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(&value)),
+    };
+    let (mantissa, exp) = match value.find(['e', 'E']) {
+        Some(i) => (&value[..i], value[i + 1..].parse::<u32>().ok()?),
+        None => (value, 0),
+    };
+    if mantissa.is_empty() || !mantissa.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let value = mantissa
+        .parse::<i128>()
+        .ok()?
+        .checked_mul(10_i128.checked_pow(exp)?)?;
+    Some(if negative { -value } else { value })
+}
+
+// A run of capitals is a single word: `Usd` and `USD` are `usd`, `HTTPToken` is `http_token`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if !previous.is_uppercase() && previous != '_'
+                || previous.is_uppercase() && next_is_lower
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
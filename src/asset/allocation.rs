@@ -0,0 +1,87 @@
+use super::asset::{Asset, CheckedOps};
+use crate::error::Error;
+use crate::utils::wide::{to_signed, U256};
+use std::convert::TryFrom;
+
+impl<T: CheckedOps> Asset<T> {
+    /// Split the asset in parts proportional to `weights`, the parts always add up to the
+    /// original amount.
+    ///
+    /// Every part is first rounded toward zero to a whole number of minimum units (ticks). The
+    /// units left over are given one each to the parts with the largest remainder (largest
+    /// remainder method), when two parts have the same remainder the first one wins. A part with
+    /// weight 0 is always 0.
+    ///
+    /// The parts are not computed with `mul_checked` and `div_checked`: `amount * weight` can
+    /// overflow an `i128` even when the part is small, so it is computed in 256 bits. Every part
+    /// is still built with `from_raw_checked` and checked against the bound of the asset.
+    ///
+    /// Fail with `Error::DivisionByZero` if there are no weights or they are all 0.
+    pub fn allocate(self, weights: &[u64]) -> Result<Vec<Asset<T>>, Error> {
+        let total: u128 = weights.iter().map(|w| *w as u128).sum();
+        if total == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let raw = self.get_inner().to_raw();
+        let negative = raw < 0;
+        let tick = T::TICK as u128;
+        // the amount is always a multiple of the tick
+        let magnitude = raw.wrapping_abs() as u128 / tick;
+
+        let mut parts = Vec::with_capacity(weights.len());
+        let mut remainders = Vec::with_capacity(weights.len());
+        let mut allocated: u128 = 0;
+        for (i, weight) in weights.iter().enumerate() {
+            let (quotient, remainder) =
+                U256::mul_u128(magnitude, *weight as u128).div_rem_u128(total);
+            // weight <= total so the quotient is never bigger than magnitude
+            let quotient = quotient.lo;
+            allocated += quotient;
+            parts.push(quotient);
+            remainders.push((remainder, i));
+        }
+
+        let left = (magnitude - allocated) as usize;
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for (_, i) in remainders.into_iter().take(left) {
+            parts[i] += 1;
+        }
+
+        parts
+            .into_iter()
+            .map(|part| {
+                let raw = to_signed(part * tick, negative)?;
+                Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+            })
+            .collect()
+    }
+
+    /// Split the asset in `n` parts that differ at most by one minimum unit (tick) and add up to
+    /// the original amount. The units left over by the division are given to the first parts.
+    ///
+    /// Fail with `Error::DivisionByZero` if `n` is 0.
+    pub fn split_even(self, n: usize) -> Result<Vec<Asset<T>>, Error>
+    where
+        T: Copy,
+    {
+        if n == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        // the amount is always a multiple of the tick
+        let ticks = self.get_inner().to_raw() / T::TICK;
+        let parts = i128::try_from(n).map_err(|_| Error::Overflow)?;
+        let part = ticks / parts;
+        let left = ticks % parts;
+        let smaller = T::from_raw_checked(part * T::TICK)?;
+        let bigger = T::from_raw_checked((part + left.signum()) * T::TICK)?;
+        Ok((0..parts)
+            .map(|i| {
+                if i < left.abs() {
+                    Asset::from_inner(bigger)
+                } else {
+                    Asset::from_inner(smaller)
+                }
+            })
+            .collect())
+    }
+}
@@ -1,14 +1,24 @@
-use crate::fixed::FloatRounding;
+use super::factor::FloatConversion;
+use crate::error::Error;
+use crate::fixed::out_of_bound;
+use crate::rounding::RoundingMode;
+use crate::utils::parse::parse_decimal;
+use crate::utils::wide::mul_decimal_rounded;
 use std::convert::TryFrom;
 use std::convert::TryInto;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops;
-
-pub const fn get_inner_len(max_value: i128) -> usize {
-    match max_value as u128 {
-        0..=2147483647 => 4,
-        2147483648..=9223372036854775807 => 8,
-        9223372036854775808..=170141183460469231731687303715884105727 => 16,
-        _ => panic!("Too big"),
+use std::str;
+
+/// Number of bytes of the smallest integer that can store every value in `[min_value, max_value]`
+pub const fn get_inner_len(min_value: i128, max_value: i128) -> usize {
+    if min_value >= i32::MIN as i128 && max_value <= i32::MAX as i128 {
+        4
+    } else if min_value >= i64::MIN as i128 && max_value <= i64::MAX as i128 {
+        8
+    } else {
+        16
     }
 }
 
@@ -16,9 +26,9 @@ pub const fn pow_10(exp: u8) -> u128 {
     let mut x: u128 = 10;
     let mut i: u8 = 0;
     if exp == 0 {
-        return 1 as u128;
+        return 1;
     } else if exp == 1 {
-        return 10 as u128;
+        return 10;
     }
     while i < exp - 1 {
         x = x * 10;
@@ -27,22 +37,137 @@ pub const fn pow_10(exp: u8) -> u128 {
     x
 }
 
-pub trait CheckedOps: Sized {
-    fn add_checked(self, rhs: Self) -> Option<Self>;
-    fn mul_checked(self, rhs: i128) -> Option<Self>;
-    fn div_checked(self, rhs: i128) -> Option<Self>;
+/// What is known about an asset at compile time, implemented by `new_asset!` and
+/// `#[derive(Asset)]`. Every `CheckedOps` is an `AssetInfo`, so generic code can format, serialize
+/// or convert any asset:
+///
+/// ```
+/// # #[macro_use] extern crate merx;
+/// use merx::asset::{AssetInfo, CheckedOps};
+/// use merx::Asset;
+/// use std::convert::TryFrom;
+///
+/// new_asset!(usd, 2, 1_000_000);
+///
+/// fn report<T: CheckedOps>(asset: Asset<T>) -> String {
+///     let units = asset.get_inner().to_raw();
+///     format!("{} units of {} (max {} units)", units, T::NAME, T::MAX)
+/// }
+///
+/// # fn main() {
+/// let asset = Asset::<usd::Value>::try_from(10).unwrap();
+/// assert_eq!(report(asset), "1000 units of usd (max 100000000 units)");
+/// assert_eq!(<usd::Value as AssetInfo>::STORAGE_BYTES, 4);
+/// # }
+/// ```
+pub trait AssetInfo {
+    /// Name of the asset, `new_asset!` uses the name of the module
+    const NAME: &'static str;
+    /// Number of decimal digits of the asset, the minimum unit is 10^-DECIMALS
+    const DECIMALS: u32;
+    /// Minimum unit of the asset expressed in `10^-DECIMALS` units, every value is a multiple of
+    /// it: an asset with 2 decimal digits and a tick of 5 can be 0.05, 0.10, 0.15 ...
+    const TICK: i128 = 1;
+    /// Minimum unit of the asset as a `(mantissa, decimal part)` tuple: 0.05 is `(5, 2)`
+    const UNIT: (i128, u32) = (Self::TICK, Self::DECIMALS);
+    /// Upper bound in minimum units, `merx::fixed::UNBOUNDED` if the asset has no upper bound
+    const MAX: i128;
+    /// Lower bound in minimum units, `merx::fixed::UNBOUNDED_MIN` if the asset has no lower bound
+    const MIN: i128;
+    /// Number of bytes used to store a value: 4, 8 or 16 for the assets of `new_asset!`, the size
+    /// of the integer for the primitive integers
+    const STORAGE_BYTES: usize;
+    /// Code of the asset, like an ISO 4217 code
+    const CODE: Option<&'static str> = None;
+    /// Symbol used to display the asset
+    const SYMBOL: Option<&'static str> = None;
+    /// Rounding mode to use when no other mode is given, `HalfEven` if the definition of the asset
+    /// does not set it
+    const ROUNDING: RoundingMode = RoundingMode::HalfEven;
+}
+
+pub trait CheckedOps: AssetInfo + Sized {
+    fn add_checked(self, rhs: Self) -> Result<Self, Error>;
+    fn sub_checked(self, rhs: Self) -> Result<Self, Error>;
+    fn neg_checked(self) -> Result<Self, Error>;
+    fn mul_checked(self, rhs: i128) -> Result<Self, Error>;
+    fn div_checked(self, rhs: i128) -> Result<Self, Error>;
     fn is_positive(&self) -> bool;
+    /// Return the value expressed in minimum units
+    fn to_raw(&self) -> i128;
+    /// Build a value from an amount expressed in minimum units
+    fn from_raw_checked(raw: i128) -> Result<Self, Error>;
+
+    /// The value nearest to the exceeded bound that is a multiple of the tick: `MIN` if
+    /// `negative`, `MAX` otherwise.
+    fn saturated(negative: bool) -> Self {
+        let bound = if negative { Self::MIN } else { Self::MAX };
+        // toward zero, 0 is always in bound
+        Self::from_raw_checked(bound - bound % Self::TICK).expect("0 is in the bound of an asset")
+    }
+
+    // The overflowing operations try the checked operation first, so when the result is in
+    // bound they cost as much as the checked ones, and clamp the result only when it fails. The
+    // returned flag is true if the result has been clamped.
+
+    fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let negative = !rhs.is_positive();
+        overflowing(self.add_checked(rhs), negative)
+    }
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let negative = rhs.is_positive();
+        overflowing(self.sub_checked(rhs), negative)
+    }
+    fn overflowing_neg(self) -> (Self, bool) {
+        let negative = self.is_positive();
+        overflowing(self.neg_checked(), negative)
+    }
+    fn overflowing_mul(self, rhs: i128) -> (Self, bool) {
+        let negative = self.is_positive() != (rhs >= 0);
+        overflowing(self.mul_checked(rhs), negative)
+    }
+    /// Panic if `rhs` is 0, like the integer division
+    fn overflowing_div(self, rhs: i128) -> (Self, bool) {
+        assert!(rhs != 0, "attempt to divide by zero");
+        let negative = self.is_positive() != (rhs > 0);
+        overflowing(self.div_checked(rhs), negative)
+    }
 }
 
-// TODO Display
-#[derive(Debug, Hash, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq)]
+/// The result of an overflowing operation: the checked result or the bound exceeded in the
+/// direction given by `negative`
+#[inline]
+fn overflowing<T: CheckedOps>(result: Result<T, Error>, negative: bool) -> (T, bool) {
+    match result {
+        Ok(value) => (value, false),
+        Err(_) => (T::saturated(negative), true),
+    }
+}
+
+/// Marker of the assets that can never be negative, declared with the `non_negative` keyword:
+/// `new_asset!(inventory, 0, 1_000_000, non_negative)`.
+///
+/// Every operation that would give a negative value fails with `Error::WouldGoNegative`, so an
+/// `Asset` of a non negative asset is always a `Credit` and can be turned in one with
+/// `Asset::into_credit`.
+pub trait NonNegative: CheckedOps {}
+
+/// A struct that defines an asset with `#[derive(Asset)]`, `Value` is the value generated for it:
+/// `Asset<<Usd as Definition>::Value>` is the same as `Asset<usd::Value>`.
+pub trait Definition {
+    type Value: CheckedOps;
+}
+
+#[derive(Debug, Hash, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 #[repr(align(8))]
-/// Wrap a numeric value smaller than 0.
+/// Wrap a numeric value smaller than 0, a `Debt` is never 0 so it does not implement `Default`.
 ///
 /// `Debt(-4) + Debt(-6) = Debt(-10)`
 ///
 /// `Debt(-4) + Credit(6) = Credit(2)`
-pub struct Debt<T: CheckedOps>(T);
+///
+/// `Debt(-4) - Credit(6) = Debt(-10)`
+pub struct Debt<T: CheckedOps>(pub(crate) T);
 
 #[derive(Debug, Hash, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq)]
 #[repr(align(8))]
@@ -50,77 +175,238 @@ pub struct Debt<T: CheckedOps>(T);
 ///
 /// `Credit(4) + Credit(6) = Credit(10)`
 ///
-/// `Credit(6) - Debt(6) = Credit(0)`
-pub struct Credit<T: CheckedOps>(T);
+/// `Credit(6) - Debt(-6) = Credit(12)`
+///
+/// `Credit(6) + Debt(-6) = Credit(0)`
+pub struct Credit<T: CheckedOps>(pub(crate) T);
 
 // Credit + Credit -> Credit?
 impl<T: CheckedOps> ops::Add<Credit<T>> for Credit<T> {
-    type Output = Option<Credit<T>>;
+    type Output = Result<Credit<T>, Error>;
 
     #[inline]
     fn add(self, _rhs: Credit<T>) -> Self::Output {
-        Some(Credit(self.0.add_checked(_rhs.0)?))
+        Ok(Credit(self.0.add_checked(_rhs.0)?))
     }
 }
 
 // Debt + Debt -> Debt?
 impl<T: CheckedOps> ops::Add<Debt<T>> for Debt<T> {
-    type Output = Option<Debt<T>>;
+    type Output = Result<Debt<T>, Error>;
 
     #[inline]
     fn add(self, _rhs: Debt<T>) -> Self::Output {
-        Some(Debt(self.0.add_checked(_rhs.0)?))
+        Ok(Debt(self.0.add_checked(_rhs.0)?))
     }
 }
 
-// Credit - Debt -> Asset?
-impl<T: CheckedOps> ops::Sub<Debt<T>> for Credit<T> {
-    type Output = Option<Asset<T>>;
+// Credit + Debt -> Asset?
+impl<T: CheckedOps> ops::Add<Debt<T>> for Credit<T> {
+    type Output = Result<Asset<T>, Error>;
 
     #[inline]
-    fn sub(self, _rhs: Debt<T>) -> Self::Output {
-        let value = self.0.add_checked(_rhs.0)?;
-        if value.is_positive() {
-            Some(Asset::Credit(Credit(value)))
-        } else {
-            Some(Asset::Debt(Debt(value)))
-        }
+    fn add(self, _rhs: Debt<T>) -> Self::Output {
+        Ok(Asset::from_inner(self.0.add_checked(_rhs.0)?))
     }
 }
 
 // Debt + Credit -> Asset?
 impl<T: CheckedOps> ops::Add<Credit<T>> for Debt<T> {
-    type Output = Option<Asset<T>>;
+    type Output = Result<Asset<T>, Error>;
 
     #[inline]
     fn add(self, _rhs: Credit<T>) -> Self::Output {
-        let value = self.0.add_checked(_rhs.0)?;
+        Ok(Asset::from_inner(self.0.add_checked(_rhs.0)?))
+    }
+}
+
+// Credit - Credit -> Asset?
+impl<T: CheckedOps> ops::Sub<Credit<T>> for Credit<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn sub(self, _rhs: Credit<T>) -> Self::Output {
+        Ok(Asset::from_inner(self.0.sub_checked(_rhs.0)?))
+    }
+}
+
+// Debt - Debt -> Asset?
+impl<T: CheckedOps> ops::Sub<Debt<T>> for Debt<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn sub(self, _rhs: Debt<T>) -> Self::Output {
+        Ok(Asset::from_inner(self.0.sub_checked(_rhs.0)?))
+    }
+}
+
+// Credit - Debt -> Credit?
+impl<T: CheckedOps> ops::Sub<Debt<T>> for Credit<T> {
+    type Output = Result<Credit<T>, Error>;
+
+    #[inline]
+    fn sub(self, _rhs: Debt<T>) -> Self::Output {
+        Ok(Credit(self.0.sub_checked(_rhs.0)?))
+    }
+}
+
+// Debt - Credit -> Debt?
+impl<T: CheckedOps> ops::Sub<Credit<T>> for Debt<T> {
+    type Output = Result<Debt<T>, Error>;
+
+    #[inline]
+    fn sub(self, _rhs: Credit<T>) -> Self::Output {
+        Ok(Debt(self.0.sub_checked(_rhs.0)?))
+    }
+}
+
+// -Credit -> Debt?
+//
+// A `Debt` is never 0, so the opposite of `Credit(0)` is an `Error::WrongSign`: `-Asset` gives
+// `Credit(0)` for it
+impl<T: CheckedOps> ops::Neg for Credit<T> {
+    type Output = Result<Debt<T>, Error>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        let value = self.0.neg_checked()?;
         if value.is_positive() {
-            Some(Asset::Credit(Credit(value)))
-        } else {
-            Some(Asset::Debt(Debt(value)))
+            return Err(Error::WrongSign);
         }
+        Ok(Debt(value))
     }
 }
 
-#[derive(Debug, Hash, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+// -Debt -> Credit?
+impl<T: CheckedOps> ops::Neg for Debt<T> {
+    type Output = Result<Credit<T>, Error>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Ok(Credit(self.0.neg_checked()?))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
 #[repr(align(8))]
 /// Can be either a `Credit` or a `Debt`
 ///
-/// Assets that wraps the same type can be added: a `Debt` is always less then 0 and a `Credit` is
-/// always bigger or equal to 0, so only the addition is supported.
+/// Assets that wraps the same type can be added, subtracted and negated. A `Debt` is always less
+/// then 0 and a `Credit` is always bigger or equal to 0, so when the sign of the result is known
+/// the operation returns a `Credit` or a `Debt` instead of an `Asset`: `Credit - Debt` is a
+/// `Credit`, `-Debt` is a `Credit` and `Asset::abs` is a `Credit`.
 ///
-/// Assets can be multiplied or divided for `i128` and TODO for floating point
+/// Assets can be multiplied or divided for `i128`, for decimal factors and for floating point
 ///
 /// Assets can be created from integer values or tuples TODO link
 ///
 /// The value of an assets can be extract as an `i128` or as a tuple TODO link
+///
+/// Assets are compared, ordered and hashed by their numeric value.
 pub enum Asset<T: CheckedOps> {
     Debt(Debt<T>),
     Credit(Credit<T>),
 }
 
+impl<T: CheckedOps + PartialEq> PartialEq for Asset<T> {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.inner() == rhs.inner()
+    }
+}
+
+impl<T: CheckedOps + Eq> Eq for Asset<T> {}
+
+impl<T: CheckedOps + PartialOrd> PartialOrd for Asset<T> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        self.inner().partial_cmp(rhs.inner())
+    }
+}
+
+impl<T: CheckedOps + Ord> Ord for Asset<T> {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.inner().cmp(rhs.inner())
+    }
+}
+
+impl<T: CheckedOps + Hash> Hash for Asset<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state)
+    }
+}
+
+// Credit == Debt?
+impl<T: CheckedOps + PartialEq> PartialEq<Debt<T>> for Credit<T> {
+    #[inline]
+    fn eq(&self, rhs: &Debt<T>) -> bool {
+        self.0 == rhs.0
+    }
+}
+
+// Debt == Credit?
+impl<T: CheckedOps + PartialEq> PartialEq<Credit<T>> for Debt<T> {
+    #[inline]
+    fn eq(&self, rhs: &Credit<T>) -> bool {
+        self.0 == rhs.0
+    }
+}
+
+// Credit <=> Debt?
+impl<T: CheckedOps + PartialOrd> PartialOrd<Debt<T>> for Credit<T> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Debt<T>) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+// Debt <=> Credit?
+impl<T: CheckedOps + PartialOrd> PartialOrd<Credit<T>> for Debt<T> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Credit<T>) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&rhs.0)
+    }
+}
+
+impl<T: CheckedOps + fmt::Display> fmt::Display for Credit<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: CheckedOps + fmt::Display> fmt::Display for Debt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Print the amount as a decimal padded to the precision of the asset: `10.50`, `-0.05`.
+///
+/// `{:+}`, width, fill and `{:.N}` are supported, `{:.N}` rounds half away from zero when N is
+/// smaller than the precision of the asset. Without `{:.N}` the output can be parsed back to the
+/// same value.
+impl<T: CheckedOps + fmt::Display> fmt::Display for Asset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Debt(x) => fmt::Display::fmt(x, f),
+            Self::Credit(x) => fmt::Display::fmt(x, f),
+        }
+    }
+}
+
 impl<T: CheckedOps> Asset<T> {
+    #[inline]
+    /// Wrap the value in a `Credit` if it is positive or in a `Debt` otherwise
+    pub fn from_inner(value: T) -> Self {
+        if value.is_positive() {
+            Asset::Credit(Credit(value))
+        } else {
+            Asset::Debt(Debt(value))
+        }
+    }
+
     #[inline]
     /// Return the numeric value of the Asset
     pub fn get_inner(self) -> T {
@@ -129,139 +415,360 @@ impl<T: CheckedOps> Asset<T> {
             Self::Credit(x) => x.0,
         }
     }
+
+    #[inline]
+    pub(crate) fn inner(&self) -> &T {
+        match self {
+            Self::Debt(x) => &x.0,
+            Self::Credit(x) => &x.0,
+        }
+    }
+
+    #[inline]
+    /// Return the absolute value of the Asset, fail if the opposite of a `Debt` is out of bound
+    pub fn abs(self) -> Result<Credit<T>, Error> {
+        match self {
+            Self::Debt(x) => -x,
+            Self::Credit(x) => Ok(x),
+        }
+    }
 }
 
 // Asset + Asset -> Asset?
 impl<T: CheckedOps> ops::Add<Asset<T>> for Asset<T> {
-    type Output = Option<Asset<T>>;
+    type Output = Result<Asset<T>, Error>;
 
     #[inline]
     fn add(self, _rhs: Asset<T>) -> Self::Output {
         let lhs = self.get_inner();
         let rhs = _rhs.get_inner();
-        let value = lhs.add_checked(rhs)?;
-        if value.is_positive() {
-            Some(Asset::Credit(Credit(value)))
-        } else {
-            Some(Asset::Debt(Debt(value)))
+        Ok(Asset::from_inner(lhs.add_checked(rhs)?))
+    }
+}
+
+// Asset - Asset -> Asset?
+impl<T: CheckedOps> ops::Sub<Asset<T>> for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn sub(self, _rhs: Asset<T>) -> Self::Output {
+        let lhs = self.get_inner();
+        let rhs = _rhs.get_inner();
+        Ok(Asset::from_inner(lhs.sub_checked(rhs)?))
+    }
+}
+
+// -Asset -> Asset?
+impl<T: CheckedOps> ops::Neg for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Ok(Asset::from_inner(self.get_inner().neg_checked()?))
+    }
+}
+
+impl<T: NonNegative> Asset<T> {
+    #[inline]
+    /// Return the `Credit` wrapped by the asset, an asset that can not be negative is never a
+    /// `Debt` different from 0
+    pub fn into_credit(self) -> Credit<T> {
+        match self {
+            Self::Debt(x) => Credit(x.0),
+            Self::Credit(x) => x,
         }
     }
 }
 
+impl<T: NonNegative> From<Asset<T>> for Credit<T> {
+    #[inline]
+    fn from(asset: Asset<T>) -> Self {
+        asset.into_credit()
+    }
+}
+
+impl<T: NonNegative> Credit<T> {
+    #[inline]
+    /// Subtract two credits of an asset that can not be negative, fail with
+    /// `Error::WouldGoNegative` if `rhs` is bigger than `self`
+    pub fn checked_sub(self, rhs: Credit<T>) -> Result<Credit<T>, Error> {
+        Ok(Credit(self.0.sub_checked(rhs.0)?))
+    }
+}
+
+impl<T: NonNegative> TryFrom<i128> for Credit<T> {
+    type Error = Error;
+
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        Ok(Asset::try_from(value)?.into_credit())
+    }
+}
+
+impl<T: NonNegative> TryFrom<&str> for Credit<T> {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(Asset::try_from(value)?.into_credit())
+    }
+}
+
 impl<T: CheckedOps> ops::Mul<i128> for Asset<T> {
-    type Output = Option<Asset<T>>;
+    type Output = Result<Asset<T>, Error>;
 
     #[inline]
     fn mul(self, rhs: i128) -> Self::Output {
         let lhs = self.get_inner();
         let value = lhs.mul_checked(rhs)?;
         if value.is_positive() {
-            Some(Asset::Credit(Credit(value)))
+            Ok(Asset::Credit(Credit(value)))
         } else {
-            Some(Asset::Debt(Debt(value)))
+            Ok(Asset::Debt(Debt(value)))
         }
     }
 }
 
 impl<T: CheckedOps> ops::Div<i128> for Asset<T> {
-    type Output = Option<Asset<T>>;
+    type Output = Result<Asset<T>, Error>;
 
     #[inline]
     fn div(self, rhs: i128) -> Self::Output {
         let lhs = self.get_inner();
         let value = lhs.div_checked(rhs)?;
         if value.is_positive() {
-            Some(Asset::Credit(Credit(value)))
+            Ok(Asset::Credit(Credit(value)))
         } else {
-            Some(Asset::Debt(Debt(value)))
+            Ok(Asset::Debt(Debt(value)))
         }
     }
 }
 
-impl<T: TryFrom<i128> + CheckedOps> TryFrom<i128> for Asset<T> {
-    type Error = ();
+// An integer number of units of the asset: `Asset::<usd::Value>::try_from(10)` is 10.00 USD
+impl<T: CheckedOps> TryFrom<i128> for Asset<T> {
+    type Error = Error;
 
     fn try_from(value: i128) -> Result<Self, Self::Error> {
-        if value >= 0 {
-            Ok(Asset::Credit(Credit(T::try_from(value).map_err(|_| ())?)))
-        } else {
-            Ok(Asset::Debt(Debt(T::try_from(value).map_err(|_| ())?)))
-        }
+        Asset::from_decimal((value, 0))
     }
 }
 
-impl<I: TryInto<i128>, F: TryInto<u128>, T: TryFrom<(i128, F)> + CheckedOps> TryFrom<(I, F)>
-    for Asset<T>
+impl<I, F, T> TryFrom<(I, F)> for Asset<T>
+where
+    I: TryInto<i128>,
+    F: TryInto<u128>,
+    T: CheckedOps,
 {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(value: (I, F)) -> Result<Self, Self::Error> {
-        let integer: i128 = (value.0).try_into().map_err(|_| ())?;
-        if integer >= 0 {
-            Ok(Asset::Credit(Credit(
-                T::try_from((integer, value.1)).map_err(|_| ())?,
-            )))
-        } else {
-            Ok(Asset::Debt(Debt(
-                T::try_from((integer, value.1)).map_err(|_| ())?,
-            )))
-        }
+        let integer: i128 = (value.0).try_into().map_err(|_| Error::Overflow)?;
+        let precision: u128 = (value.1).try_into().map_err(|_| Error::Overflow)?;
+        let precision = u32::try_from(precision).unwrap_or(u32::MAX);
+        Asset::from_decimal((integer, precision))
     }
 }
 
-impl<T: TryFrom<(i128, usize)> + CheckedOps> TryFrom<&str> for Asset<T> {
-    type Error = ();
+impl<T: CheckedOps> TryFrom<&str> for Asset<T> {
+    type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let decimal_part = match value.find(".") {
-            Some(x) => value.split_at(x + 1).1.len(),
-            None => 0,
-        };
-        let mantissa: String = value.chars().filter(|x| *x != '.').collect();
-        let value = (str::parse::<i64>(&mantissa).unwrap(), decimal_part);
-        Ok(Asset::try_from(value)?)
+        Asset::try_from(parse_decimal(value)?)
     }
 }
 
-impl<T: TryFrom<(f64, FloatRounding)> + CheckedOps> TryFrom<(f64, FloatRounding)> for Asset<T> {
-    type Error = ();
+impl<T: CheckedOps> str::FromStr for Asset<T> {
+    type Err = Error;
 
-    fn try_from(value: (f64, FloatRounding)) -> Result<Self, Self::Error> {
-        if value.0 >= 0.0 {
-            Ok(Asset::Credit(Credit(T::try_from(value).map_err(|_| ())?)))
-        } else {
-            Ok(Asset::Debt(Debt(T::try_from(value).map_err(|_| ())?)))
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Asset::try_from(value)
+    }
+}
+
+impl<T: CheckedOps> Asset<T> {
+    /// Build an asset from a `(mantissa, decimal part)` tuple, fail with `Error::PrecisionLoss`
+    /// if the tuple has more decimal digits than the asset.
+    pub fn from_decimal(value: (i128, u32)) -> Result<Self, Error> {
+        let (mantissa, scale) = value;
+        if scale > T::DECIMALS {
+            return Err(Error::PrecisionLoss {
+                given: scale,
+                supported: T::DECIMALS,
+            });
         }
+        let raw = mantissa
+            .checked_mul(10_i128.pow(T::DECIMALS - scale))
+            .ok_or_else(|| out_of_bound(mantissa < 0, T::MIN, T::MAX))?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+
+    /// Build an asset from a `(mantissa, decimal part)` tuple, if the tuple has more decimal
+    /// digits than the asset the value is rounded with `rounding`.
+    pub fn from_decimal_rounded(value: (i128, u32), rounding: RoundingMode) -> Result<Self, Error> {
+        let raw = mul_decimal_rounded(10_i128.pow(T::DECIMALS), value, rounding, T::TICK as u128)?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+
+    /// Parse a decimal string, if the string has more decimal digits than the asset the value is
+    /// rounded with `rounding`.
+    pub fn parse_rounded(value: &str, rounding: RoundingMode) -> Result<Self, Error> {
+        Self::from_decimal_rounded(parse_decimal(value)?, rounding)
     }
 }
 
-impl<V: CheckedOps + TryFrom<i128>> Asset<V> {
+impl<T: CheckedOps> TryFrom<(f64, RoundingMode)> for Asset<T> {
+    type Error = Error;
+
+    fn try_from(value: (f64, RoundingMode)) -> Result<Self, Self::Error> {
+        Self::from_f64(value.0, value.1, FloatConversion::Shortest)
+    }
+}
+
+impl<V: CheckedOps> Asset<V> {
     pub fn new<T: Into<i128>>(val: T) -> Option<Self> {
         let val: i128 = val.into();
         Self::try_from(val).ok()
     }
 }
 
+/// Define an asset in a module with its `Value`: `new_asset!(usd, 2, 1_000_000)` is an asset with
+/// 2 decimal digits in [-1_000_000, 1_000_000].
+///
+/// An invalid bound is a compile error, like a symmetric bound that is not bigger than 0:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate merx;
+/// new_asset!(usd, 2, 0);
+/// # fn main() {}
+/// ```
 #[allow(unused_macros)]
 #[macro_export]
 macro_rules! new_asset {
-    // TODO maybe Value should be defined outside the macro??
+    // An asset that can never be negative: the lower bound is 0 and `Value` implements
+    // `NonNegative`: `new_asset!(inventory, 0, 1_000_000, non_negative)`
+    ($mod_name:ident, $frac:tt, non_negative) => {
+        $crate::new_asset!($mod_name, $frac, non_negative, tick = 1);
+    };
+    ($mod_name:ident, $frac:tt, non_negative, tick = $tick:tt) => {
+        $crate::new_asset!(
+            @impl $mod_name,
+            $frac,
+            0,
+            $crate::fixed::UNBOUNDED,
+            $tick,
+            non_negative
+        );
+    };
+    ($mod_name:ident, $frac:tt, $max_value:tt, non_negative) => {
+        $crate::new_asset!($mod_name, $frac, $max_value, non_negative, tick = 1);
+    };
+    ($mod_name:ident, $frac:tt, $max_value:tt, non_negative, tick = $tick:tt) => {
+        $crate::new_asset!(
+            @impl $mod_name,
+            $frac,
+            0,
+            {
+                assert!($max_value as i128 >= 0);
+                assert!(($max_value as i128) <= i128::MAX / POW as i128);
+                $max_value as i128 * POW as i128
+            },
+            $tick,
+            non_negative
+        );
+    };
+    // An asset without bounds, limited only by the range of the i128 storage
+    ($mod_name:ident, $frac:tt) => {
+        $crate::new_asset!($mod_name, $frac, unbounded, tick = 1);
+    };
+    ($mod_name:ident, $frac:tt, tick = $tick:tt) => {
+        $crate::new_asset!($mod_name, $frac, unbounded, tick = $tick);
+    };
+    ($mod_name:ident, $frac:tt, unbounded, tick = $tick:tt) => {
+        $crate::new_asset!(
+            @impl $mod_name,
+            $frac,
+            $crate::fixed::UNBOUNDED_MIN,
+            $crate::fixed::UNBOUNDED,
+            $tick,
+            signed
+        );
+    };
+    // An asset with independent bounds expressed in units of the asset:
+    // `new_asset!(wallet, 2, min = -500, max = 1_000_000)`
+    ($mod_name:ident, $frac:tt, min = $min_value:expr, max = $max_value:expr) => {
+        $crate::new_asset!($mod_name, $frac, min = $min_value, max = $max_value, tick = 1);
+    };
+    ($mod_name:ident, $frac:tt, min = $min_value:expr, max = $max_value:expr, tick = $tick:tt) => {
+        $crate::new_asset!(
+            @impl $mod_name,
+            $frac,
+            {
+                assert!(($min_value as i128) <= 0);
+                assert!(($min_value as i128) >= i128::MIN / POW as i128);
+                $min_value as i128 * POW as i128
+            },
+            {
+                assert!(($max_value as i128) >= 0);
+                assert!(($max_value as i128) <= i128::MAX / POW as i128);
+                $max_value as i128 * POW as i128
+            },
+            $tick,
+            signed
+        );
+    };
+    // An asset with a bound symmetric around zero: `new_asset!(usd, 2, 1_000_000)` is in
+    // [-1_000_000, 1_000_000]
     ($mod_name:ident, $frac:tt, $max_value:tt) => {
-        mod $mod_name {
-            use super::ArrayWrapper;
-            use super::Fixed;
-            use super::FloatRounding;
-            use super::HasBound;
-            use super::IsFixed;
+        $crate::new_asset!($mod_name, $frac, $max_value, tick = 1);
+    };
+    // The minimum unit is tick * 10^-frac: `new_asset!(chf, 2, 1_000_000, tick = 5)` is rounded
+    // to 0.05
+    ($mod_name:ident, $frac:tt, $max_value:tt, tick = $tick:tt) => {
+        $crate::new_asset!(
+            @impl $mod_name,
+            $frac,
+            -MAX_VAL,
+            {
+                // a symmetric bound of 0 would only hold 0
+                assert!(($max_value as i128) > 0);
+                assert!(($max_value as i128) <= i128::MAX / POW as i128);
+                $max_value as i128 * POW as i128
+            },
+            $tick,
+            signed
+        );
+    };
+    // TODO maybe Value should be defined outside the macro??
+    (@kind non_negative) => {
+        impl $crate::asset::NonNegative for Value {}
+    };
+    (@kind signed) => {};
+    // `#[derive(Asset)]` expands here with the visibility of the struct
+    (@option) => {
+        None
+    };
+    (@option $value:expr) => {
+        Some($value)
+    };
+    (@rounding) => {
+        $crate::RoundingMode::HalfEven
+    };
+    (@rounding $rounding:ident) => {
+        $crate::RoundingMode::$rounding
+    };
+    (@impl $vis:vis $mod_name:ident, $frac:tt, $min_val:expr, $max_val:expr, $tick:tt, $kind:ident
+        $(, code = $code:expr)? $(, symbol = $symbol:expr)? $(, rounding = $rounding:ident)?) => {
+        $vis mod $mod_name {
             use std::convert::TryFrom;
             use std::convert::TryInto;
             use std::fmt;
             use $crate::asset::get_inner_len;
             use $crate::asset::pow_10;
             use $crate::asset::Asset;
+            use $crate::asset::AssetInfo;
             use $crate::asset::CheckedOps;
             use $crate::asset::Credit;
             use $crate::asset::Debt;
+            use $crate::fixed::{ArrayWrapper, Fixed, HasBound, IsFixed};
+            use $crate::RoundingMode;
             //const FRAC_B2: u128 = ((332192809489 as u128 * $frac as u128) / pow_10(11)) + 1;
             //const FRAC: usize = $frac;
 
@@ -271,24 +778,38 @@ macro_rules! new_asset {
             };
             #[allow(unused_comparisons)]
             const MAX_VAL: i128 = {
-                assert!($max_value as i128 >= 0);
                 assert!($frac as i128 >= 0);
-                assert!((i128::max_value() / $max_value) > POW as i128);
-                $max_value * POW as i128
+                $max_val
+            };
+            #[allow(unused_comparisons)]
+            const MIN_VAL: i128 = $min_val;
+            const LEN: usize = get_inner_len(MIN_VAL, MAX_VAL);
+            const TICK: i128 = {
+                assert!($tick as i128 > 0);
+                $tick
             };
-            const LEN: usize = get_inner_len(MAX_VAL);
 
-            pub type Fixed_ = Fixed<LEN, MAX_VAL, POW>;
-            impl HasBound<MAX_VAL, LEN> for Fixed_ {}
-            impl IsFixed<LEN, MAX_VAL, POW> for Fixed_ {}
+            /// Fail if the value is not a multiple of the tick
+            #[inline(always)]
+            #[allow(clippy::modulo_one)]
+            fn check_tick(value: Value) -> Result<Value, $crate::Error> {
+                if TICK == 1 || value.to_raw() % TICK == 0 {
+                    Ok(value)
+                } else {
+                    Err($crate::Error::NotMultipleOfTick { tick: TICK })
+                }
+            }
+
+            pub type Fixed_ = Fixed<LEN, MIN_VAL, MAX_VAL, POW>;
 
             pub fn inspect() {
                 println!("POW: {}", POW);
+                println!("MIN_VAL: {}", MIN_VAL);
                 println!("MAX_VAL: {}", MAX_VAL);
                 println!("LEN: {}", LEN);
             }
 
-            #[derive(PartialEq, Copy, Clone)]
+            #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
             #[repr(align(8))]
             pub struct Value(pub Fixed_);
 
@@ -304,61 +825,95 @@ macro_rules! new_asset {
 
             impl IsFixed<LEN, MAX_VAL, POW> for Value {}
 
+            $crate::new_asset!(@kind $kind);
+
             // TODO is possible avoid to reimplement everything for the inner value? Maybe
             // implementing Deref?
             impl TryFrom<i128> for Value {
-                type Error = ();
+                type Error = $crate::Error;
 
                 fn try_from(value: i128) -> Result<Self, Self::Error> {
-                    Ok(Value(Fixed_::try_from(value)?))
+                    check_tick(Value(Fixed_::try_from(value)?))
                 }
             }
 
-            impl TryFrom<(f64, FloatRounding)> for Value {
-                type Error = ();
+            impl TryFrom<(f64, RoundingMode)> for Value {
+                type Error = $crate::Error;
 
-                fn try_from(value: (f64, FloatRounding)) -> Result<Self, Self::Error> {
-                    Ok(Value(Fixed_::try_from(value)?))
+                fn try_from(value: (f64, RoundingMode)) -> Result<Self, Self::Error> {
+                    Ok(Asset::<Value>::try_from(value)?.get_inner())
                 }
             }
 
             impl<T: TryInto<u128>> TryFrom<(i128, T)> for Value {
-                type Error = ();
+                type Error = $crate::Error;
 
                 fn try_from(value: (i128, T)) -> Result<Self, Self::Error> {
-                    let precision: u128 = (value.1).try_into().map_err(|_| ())?;
-                    Ok(Value(Fixed_::try_from((value.0, precision))?))
+                    let precision: u128 =
+                        (value.1).try_into().map_err(|_| $crate::Error::Overflow)?;
+                    check_tick(Value(Fixed_::try_from((value.0, precision))?))
                 }
             }
 
+            impl AssetInfo for Value {
+                const NAME: &'static str = stringify!($mod_name);
+                const DECIMALS: u32 = $frac;
+                const TICK: i128 = TICK;
+                const MAX: i128 = MAX_VAL;
+                const MIN: i128 = MIN_VAL;
+                const STORAGE_BYTES: usize = LEN;
+                const CODE: Option<&'static str> = $crate::new_asset!(@option $($code)?);
+                const SYMBOL: Option<&'static str> = $crate::new_asset!(@option $($symbol)?);
+                const ROUNDING: $crate::RoundingMode = $crate::new_asset!(@rounding $($rounding)?);
+            }
+
             impl CheckedOps for Value {
                 #[inline]
-                fn add_checked(self, rhs: Self) -> Option<Self> {
-                    Some(Self(self.0.add_checked(rhs.0)?))
+                fn add_checked(self, rhs: Self) -> Result<Self, $crate::Error> {
+                    Ok(Self(self.0.add_checked(rhs.0)?))
+                }
+                #[inline]
+                fn sub_checked(self, rhs: Self) -> Result<Self, $crate::Error> {
+                    Ok(Self(self.0.sub_checked(rhs.0)?))
                 }
                 #[inline]
-                fn mul_checked(self, rhs: i128) -> Option<Self> {
-                    Some(Self(self.0.mul_checked(rhs)?))
+                fn neg_checked(self) -> Result<Self, $crate::Error> {
+                    Ok(Self(self.0.neg_checked()?))
                 }
                 #[inline]
-                fn div_checked(self, rhs: i128) -> Option<Self> {
-                    Some(Self(self.0.div_checked(rhs)?))
+                fn mul_checked(self, rhs: i128) -> Result<Self, $crate::Error> {
+                    Ok(Self(self.0.mul_checked(rhs)?))
+                }
+                #[inline]
+                fn div_checked(self, rhs: i128) -> Result<Self, $crate::Error> {
+                    if TICK == 1 {
+                        return Ok(Self(self.0.div_checked(rhs)?));
+                    }
+                    // round toward zero to a multiple of the tick
+                    let ticks = self.to_raw() / TICK;
+                    if rhs == 0 {
+                        return Err($crate::Error::DivisionByZero);
+                    }
+                    let ticks = ticks.checked_div(rhs).ok_or($crate::Error::Overflow)?;
+                    Self::from_raw_checked(ticks * TICK)
                 }
                 #[inline]
                 fn is_positive(&self) -> bool {
                     self.0.is_positive()
                 }
-            }
-
-            use super::FixedToInt;
-
-            impl FixedToInt for Asset<Value> {
-                fn to_parts(self) -> (i128, i128, u128) {
-                    self.get_inner().to_parts()
+                #[inline]
+                fn to_raw(&self) -> i128 {
+                    self.0.to_raw()
+                }
+                #[inline]
+                fn from_raw_checked(raw: i128) -> Result<Self, $crate::Error> {
+                    check_tick(Self(Fixed_::from_raw_checked(raw)?))
                 }
+            }
 
-                fn to_int(self) -> i128 {
-                    self.to_parts().0
+            impl fmt::Display for Value {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    $crate::utils::numeric_methods::fmt_decimal(f, self.to_raw(), $frac)
                 }
             }
 
@@ -372,18 +927,54 @@ macro_rules! new_asset {
                         .finish()
                 }
             }
+
+            $crate::__impl_serde!(Value);
         }
     };
 }
 
+// The feature is checked here and not in `new_asset!` because the macro is expanded in the crate
+// of the user, that do not have a `serde` feature.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
 #[macro_export]
-macro_rules! get_traits {
-    () => {
-        pub trait FixedToInt {
-            fn to_parts(self) -> (i128, i128, u128);
+macro_rules! __impl_serde {
+    ($value:ident) => {
+        impl $crate::__serde::Serialize for $value {
+            fn serialize<S: $crate::__serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                $crate::serde::decimal::serialize(&$crate::Asset::from_inner(*self), serializer)
+            }
+        }
 
-            fn to_int(self) -> i128;
+        impl<'de> $crate::__serde::Deserialize<'de> for $value {
+            fn deserialize<D: $crate::__serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                let asset: $crate::Asset<$value> =
+                    $crate::serde::decimal::deserialize(deserializer)?;
+                Ok(asset.get_inner())
+            }
         }
-        get_fixed!();
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_serde {
+    ($value:ident) => {};
+}
+
+/// `Fixed` and `FixedToInt` are defined in `merx::fixed` and `new_asset!` does not need them in
+/// scope anymore. This macro only imports them, so that the crates that still call it compile.
+#[macro_export]
+macro_rules! get_traits {
+    () => {
+        $crate::get_fixed!();
+        #[allow(unused_imports)]
+        use $crate::fixed::FixedToInt;
     };
 }
@@ -0,0 +1,358 @@
+use super::asset::{Asset, AssetInfo, CheckedOps};
+use crate::error::Error;
+use crate::fixed::{is_in_bound, out_of_bound, UNBOUNDED, UNBOUNDED_MIN};
+use crate::rounding::RoundingMode;
+use crate::utils::numeric_methods::fmt_decimal;
+use crate::utils::parse::parse_decimal;
+use crate::utils::wide::{div_decimal_rounded, mul_decimal_rounded};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops;
+use std::sync::Arc;
+
+/// The definition of an asset known only at runtime, like a token whose decimals and supply cap
+/// are read from on-chain metadata. It is the runtime version of `AssetInfo`: the bounds and the
+/// tick are expressed in minimum units.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetSpec {
+    id: String,
+    decimals: u32,
+    min: i128,
+    max: i128,
+    tick: i128,
+}
+
+impl AssetSpec {
+    /// An asset with `decimals` decimal digits in `[min, max]` minimum units, `min` can not be
+    /// positive and `max` can not be negative.
+    pub fn new(id: impl Into<String>, decimals: u32, min: i128, max: i128) -> Result<Self, Error> {
+        if decimals > 38 {
+            return Err(Error::PrecisionLoss {
+                given: decimals,
+                supported: 38,
+            });
+        }
+        if min > 0 || max < 0 {
+            return Err(Error::InvalidSpec {
+                reason: "the bounds must contain 0",
+            });
+        }
+        Ok(AssetSpec {
+            id: id.into(),
+            decimals,
+            min,
+            max,
+            tick: 1,
+        })
+    }
+
+    /// An asset without bounds, limited only by the range of i128
+    pub fn unbounded(id: impl Into<String>, decimals: u32) -> Result<Self, Error> {
+        Self::new(id, decimals, UNBOUNDED_MIN, UNBOUNDED)
+    }
+
+    /// Set the minimum unit of the asset in `10^-decimals` units, every value must be a multiple
+    /// of it.
+    pub fn with_tick(mut self, tick: i128) -> Result<Self, Error> {
+        if tick <= 0 {
+            return Err(Error::InvalidSpec {
+                reason: "the tick must be positive",
+            });
+        }
+        self.tick = tick;
+        Ok(self)
+    }
+
+    /// The spec of a static asset, its id is `T::NAME`
+    pub fn of<T: AssetInfo>() -> Self {
+        AssetSpec {
+            id: T::NAME.to_string(),
+            decimals: T::DECIMALS,
+            min: T::MIN,
+            max: T::MAX,
+            tick: T::TICK,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    pub fn min(&self) -> i128 {
+        self.min
+    }
+
+    pub fn max(&self) -> i128 {
+        self.max
+    }
+
+    pub fn tick(&self) -> i128 {
+        self.tick
+    }
+
+    /// Check the bound and the tick of an amount in minimum units
+    #[inline]
+    fn check(&self, raw: i128) -> Result<i128, Error> {
+        if !is_in_bound(raw, self.min, self.max) {
+            return Err(out_of_bound(raw < 0, self.min, self.max));
+        }
+        if raw % self.tick != 0 {
+            return Err(Error::NotMultipleOfTick { tick: self.tick });
+        }
+        Ok(raw)
+    }
+}
+
+/// An amount of an asset defined at runtime by an `AssetSpec`.
+///
+/// It has the same checked semantic of `Asset`: every operation returns a `Result` and fails if
+/// the result is out of the bound of the spec, operations between amounts of different specs
+/// fail with `Error::DifferentAssets`.
+///
+/// ```
+/// use merx::asset::{AssetSpec, DynAsset};
+/// use std::sync::Arc;
+///
+/// // a token with 18 decimals and a supply cap of 1_000_000 tokens
+/// let spec = Arc::new(AssetSpec::new("0xdac1", 18, 0, 1_000_000 * 10_i128.pow(18)).unwrap());
+/// let a = DynAsset::parse(&spec, "0.5").unwrap();
+/// let b = DynAsset::parse(&spec, "1.25").unwrap();
+/// assert_eq!((a + b).unwrap().to_string(), "1.750000000000000000");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DynAsset {
+    spec: Arc<AssetSpec>,
+    raw: i128,
+}
+
+impl DynAsset {
+    /// Build an amount from minimum units
+    pub fn from_raw(spec: &Arc<AssetSpec>, raw: i128) -> Result<Self, Error> {
+        Ok(DynAsset {
+            spec: spec.clone(),
+            raw: spec.check(raw)?,
+        })
+    }
+
+    /// Build an amount from a `(mantissa, decimal part)` tuple, fail with `Error::PrecisionLoss`
+    /// if the tuple has more decimal digits than the spec.
+    pub fn from_decimal(spec: &Arc<AssetSpec>, value: (i128, u32)) -> Result<Self, Error> {
+        let (mantissa, scale) = value;
+        if scale > spec.decimals {
+            return Err(Error::PrecisionLoss {
+                given: scale,
+                supported: spec.decimals,
+            });
+        }
+        let raw = mantissa
+            .checked_mul(10_i128.pow(spec.decimals - scale))
+            .ok_or_else(|| out_of_bound(mantissa < 0, spec.min, spec.max))?;
+        Self::from_raw(spec, raw)
+    }
+
+    /// Build an amount from a `(mantissa, decimal part)` tuple, if the tuple has more decimal
+    /// digits than the spec the value is rounded with `rounding`.
+    pub fn from_decimal_rounded(
+        spec: &Arc<AssetSpec>,
+        value: (i128, u32),
+        rounding: RoundingMode,
+    ) -> Result<Self, Error> {
+        let pow = 10_i128.pow(spec.decimals);
+        let raw = mul_decimal_rounded(pow, value, rounding, spec.tick as u128)?;
+        Self::from_raw(spec, raw)
+    }
+
+    /// Parse a decimal string, like `Asset::try_from(&str)`
+    pub fn parse(spec: &Arc<AssetSpec>, value: &str) -> Result<Self, Error> {
+        Self::from_decimal(spec, parse_decimal(value)?)
+    }
+
+    /// Parse a decimal string, if the string has more decimal digits than the spec the value is
+    /// rounded with `rounding`.
+    pub fn parse_rounded(
+        spec: &Arc<AssetSpec>,
+        value: &str,
+        rounding: RoundingMode,
+    ) -> Result<Self, Error> {
+        Self::from_decimal_rounded(spec, parse_decimal(value)?, rounding)
+    }
+
+    /// Bridge a static asset to an amount of `spec`, fail with `Error::DifferentAssets` if `spec`
+    /// is not the spec of `T`.
+    pub fn from_asset<T: CheckedOps>(
+        spec: &Arc<AssetSpec>,
+        asset: Asset<T>,
+    ) -> Result<Self, Error> {
+        if **spec != AssetSpec::of::<T>() {
+            return Err(Error::DifferentAssets);
+        }
+        Self::from_raw(spec, asset.get_inner().to_raw())
+    }
+
+    pub fn spec(&self) -> &Arc<AssetSpec> {
+        &self.spec
+    }
+
+    /// Return the amount expressed in minimum units
+    pub fn to_raw(&self) -> i128 {
+        self.raw
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.raw < 0
+    }
+
+    #[inline]
+    fn with_raw(&self, raw: i128) -> Result<Self, Error> {
+        Ok(DynAsset {
+            spec: self.spec.clone(),
+            raw: self.spec.check(raw)?,
+        })
+    }
+
+    #[inline]
+    fn same_spec(&self, rhs: &Self) -> Result<(), Error> {
+        if Arc::ptr_eq(&self.spec, &rhs.spec) || self.spec == rhs.spec {
+            Ok(())
+        } else {
+            Err(Error::DifferentAssets)
+        }
+    }
+
+    pub fn add_checked(&self, rhs: &Self) -> Result<Self, Error> {
+        self.same_spec(rhs)?;
+        self.with_raw(self.raw.checked_add(rhs.raw).ok_or(Error::Overflow)?)
+    }
+
+    pub fn sub_checked(&self, rhs: &Self) -> Result<Self, Error> {
+        self.same_spec(rhs)?;
+        self.with_raw(self.raw.checked_sub(rhs.raw).ok_or(Error::Overflow)?)
+    }
+
+    pub fn neg_checked(&self) -> Result<Self, Error> {
+        self.with_raw(self.raw.checked_neg().ok_or(Error::Overflow)?)
+    }
+
+    pub fn mul_checked(&self, rhs: i128) -> Result<Self, Error> {
+        self.with_raw(self.raw.checked_mul(rhs).ok_or(Error::Overflow)?)
+    }
+
+    /// Divide for an integer rounding toward zero to a multiple of the tick
+    pub fn div_checked(&self, rhs: i128) -> Result<Self, Error> {
+        if rhs == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let ticks = (self.raw / self.spec.tick)
+            .checked_div(rhs)
+            .ok_or(Error::Overflow)?;
+        self.with_raw(ticks * self.spec.tick)
+    }
+
+    /// Like `Asset::div_rounded`
+    pub fn div_rounded(&self, rhs: i128, rounding: RoundingMode) -> Result<Self, Error> {
+        self.div_decimal((rhs, 0), rounding)
+    }
+
+    /// Like `Asset::mul_decimal`
+    pub fn mul_decimal(&self, factor: (i128, u32), rounding: RoundingMode) -> Result<Self, Error> {
+        let tick = self.spec.tick as u128;
+        self.with_raw(mul_decimal_rounded(self.raw, factor, rounding, tick)?)
+    }
+
+    /// Like `Asset::div_decimal`
+    pub fn div_decimal(&self, factor: (i128, u32), rounding: RoundingMode) -> Result<Self, Error> {
+        let tick = self.spec.tick as u128;
+        self.with_raw(div_decimal_rounded(self.raw, factor, rounding, tick)?)
+    }
+}
+
+// Asset<T> -> DynAsset, the spec is the spec of T
+impl<T: CheckedOps> From<Asset<T>> for DynAsset {
+    fn from(asset: Asset<T>) -> Self {
+        DynAsset {
+            spec: Arc::new(AssetSpec::of::<T>()),
+            raw: asset.get_inner().to_raw(),
+        }
+    }
+}
+
+// DynAsset -> Asset<T>? only if the DynAsset has the spec of T
+impl<T: CheckedOps> TryFrom<DynAsset> for Asset<T> {
+    type Error = Error;
+
+    fn try_from(value: DynAsset) -> Result<Self, Self::Error> {
+        if *value.spec != AssetSpec::of::<T>() {
+            return Err(Error::DifferentAssets);
+        }
+        Ok(Asset::from_inner(T::from_raw_checked(value.raw)?))
+    }
+}
+
+// Amounts of different specs are not comparable
+impl PartialOrd for DynAsset {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        self.same_spec(rhs).ok()?;
+        Some(self.raw.cmp(&rhs.raw))
+    }
+}
+
+impl fmt::Display for DynAsset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_decimal(f, self.raw, self.spec.decimals)
+    }
+}
+
+// DynAsset + DynAsset -> DynAsset?
+impl ops::Add for DynAsset {
+    type Output = Result<DynAsset, Error>;
+
+    #[inline]
+    fn add(self, rhs: DynAsset) -> Self::Output {
+        self.add_checked(&rhs)
+    }
+}
+
+// DynAsset - DynAsset -> DynAsset?
+impl ops::Sub for DynAsset {
+    type Output = Result<DynAsset, Error>;
+
+    #[inline]
+    fn sub(self, rhs: DynAsset) -> Self::Output {
+        self.sub_checked(&rhs)
+    }
+}
+
+// -DynAsset -> DynAsset?
+impl ops::Neg for DynAsset {
+    type Output = Result<DynAsset, Error>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.neg_checked()
+    }
+}
+
+// DynAsset * n -> DynAsset?
+impl ops::Mul<i128> for DynAsset {
+    type Output = Result<DynAsset, Error>;
+
+    #[inline]
+    fn mul(self, rhs: i128) -> Self::Output {
+        self.mul_checked(rhs)
+    }
+}
+
+// DynAsset / n -> DynAsset?
+impl ops::Div<i128> for DynAsset {
+    type Output = Result<DynAsset, Error>;
+
+    #[inline]
+    fn div(self, rhs: i128) -> Self::Output {
+        self.div_checked(rhs)
+    }
+}
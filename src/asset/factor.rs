@@ -0,0 +1,292 @@
+use super::asset::{Asset, CheckedOps};
+use crate::error::Error;
+use crate::fixed::out_of_bound;
+use crate::rounding::{Fraction, RoundingMode};
+use crate::utils::parse::parse_decimal;
+use crate::utils::wide::{
+    div_decimal_rounded, mul_decimal_rounded, mul_div_rounded, round_quotient, U256,
+};
+use std::cmp::Ordering;
+use std::ops;
+
+/// How a float is converted to a decimal value before it is rounded to the precision of an asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatConversion {
+    /// The shortest decimal that is parsed back to the same float, the number that was written:
+    /// 1.005 is 1.005 and rounded half up to 2 decimal digits is 1.01
+    Shortest,
+    /// The exact value of the binary float: 1.005 is
+    /// 1.00499999999999989341858963598497211933135986328125 and rounded half up to 2 decimal
+    /// digits is 1.00
+    Exact,
+}
+
+impl Default for FloatConversion {
+    fn default() -> Self {
+        FloatConversion::Shortest
+    }
+}
+
+/// Convert a finite float in the shortest `(mantissa, exponent)` pair that is parsed back to the
+/// same float, the value is `mantissa * 10^exponent`: 1.075 -> (1075, -3), 1e40 -> (1, 40).
+fn float_to_parts(value: f64) -> Result<(i128, i64), Error> {
+    if !value.is_finite() {
+        return Err(Error::NonFinite);
+    }
+    let formatted = format!("{:e}", value);
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').expect("`{:e}` has an `e`"));
+    // at most 17 significant digits, they always fit
+    let (mantissa, scale) = parse_decimal(mantissa)?;
+    let exponent: i64 = exponent[1..]
+        .parse()
+        .expect("`{:e}` has an integer exponent");
+    Ok((mantissa, exponent - scale as i64))
+}
+
+/// Convert a finite float in the shortest `(mantissa, decimal part)` tuple that is parsed back to
+/// the same float: 1.075 -> (1075, 3). Fail with `Error::Overflow` if the float is too big to be
+/// a tuple.
+pub(crate) fn float_to_decimal(value: f64) -> Result<(i128, u32), Error> {
+    let (mantissa, exponent) = float_to_parts(value)?;
+    if exponent >= 0 {
+        let mantissa = 10_i128
+            .checked_pow(exponent as u32)
+            .and_then(|pow| mantissa.checked_mul(pow))
+            .ok_or(Error::Overflow)?;
+        Ok((mantissa, 0))
+    } else {
+        Ok((mantissa, (-exponent) as u32))
+    }
+}
+
+/// `value * pow` rounded once with `rounding` to a multiple of `tick`, fail with
+/// `Error::Overflow` if the result does not fit in an i128. The rounding is done on integers, so
+/// it is correct for every finite float.
+pub(crate) fn float_to_raw(
+    value: f64,
+    pow: u128,
+    conversion: FloatConversion,
+    rounding: RoundingMode,
+    tick: u128,
+) -> Result<i128, Error> {
+    if !value.is_finite() {
+        return Err(Error::NonFinite);
+    }
+    // 2^127 is exact as a float, a value of this magnitude does not fit in an i128 with any pow
+    if value.abs() >= i128::MAX as f64 {
+        return Err(Error::Overflow);
+    }
+    match conversion {
+        FloatConversion::Shortest => {
+            mul_decimal_rounded(pow as i128, float_to_decimal(value)?, rounding, tick)
+        }
+        FloatConversion::Exact => {
+            // value = mantissa * 2^exp
+            let bits = value.to_bits();
+            let negative = bits >> 63 == 1;
+            let biased_exp = ((bits >> 52) & 0x7ff) as i64;
+            let fraction = bits & ((1 << 52) - 1);
+            let (mantissa, exp) = if biased_exp == 0 {
+                (fraction, -1074)
+            } else {
+                (fraction | (1 << 52), biased_exp - 1075)
+            };
+            let scaled = U256::mul_u128(mantissa as u128, pow);
+            let (quotient, fraction) = if exp >= 0 {
+                let shifted = scaled.checked_shl(exp as u32).ok_or(Error::Overflow)?;
+                (shifted, Fraction::Zero)
+            } else {
+                scaled.div_pow_2((-exp) as u32)
+            };
+            round_quotient(quotient, negative, fraction, rounding, tick)
+        }
+    }
+}
+
+/// Least common multiple of two positive numbers
+fn lcm(a: i128, b: i128) -> Option<i128> {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        let r = x % y;
+        x = y;
+        y = r;
+    }
+    (a / x).checked_mul(b)
+}
+
+impl<T: CheckedOps> Asset<T> {
+    /// Multiply the asset for a decimal factor expressed as a `(mantissa, decimal part)` tuple:
+    /// `(1075, 3)` is 1.075.
+    ///
+    /// The product is computed exactly and rounded once to the precision of the asset with
+    /// `rounding`, then checked against the bound of the asset.
+    pub fn mul_decimal(self, factor: (i128, u32), rounding: RoundingMode) -> Result<Self, Error> {
+        let raw =
+            mul_decimal_rounded(self.get_inner().to_raw(), factor, rounding, T::TICK as u128)?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+
+    /// Divide the asset for a decimal factor expressed as a `(mantissa, decimal part)` tuple:
+    /// `(3, 1)` is 0.3.
+    ///
+    /// The quotient is rounded once to the precision of the asset with `rounding`, then checked
+    /// against the bound of the asset.
+    pub fn div_decimal(self, factor: (i128, u32), rounding: RoundingMode) -> Result<Self, Error> {
+        let raw =
+            div_decimal_rounded(self.get_inner().to_raw(), factor, rounding, T::TICK as u128)?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+}
+
+impl<T: CheckedOps> Asset<T> {
+    /// Build an asset from a float rounded once to the precision of the asset with `rounding`,
+    /// `conversion` tells if the float is the shortest decimal that represents it or its exact
+    /// binary value. `Asset::try_from((value, rounding))` uses `FloatConversion::Shortest`.
+    ///
+    /// The bound is checked on the rounded integer, so a float can not exceed it by a rounding
+    /// error.
+    pub fn from_f64(
+        value: f64,
+        rounding: RoundingMode,
+        conversion: FloatConversion,
+    ) -> Result<Self, Error> {
+        let pow = 10_u128.pow(T::DECIMALS);
+        let raw =
+            float_to_raw(value, pow, conversion, rounding, T::TICK as u128).map_err(|error| {
+                match error {
+                    Error::Overflow => out_of_bound(value < 0.0, T::MIN, T::MAX),
+                    error => error,
+                }
+            })?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+
+    /// Divide the asset for an integer rounding the quotient with `rounding`, `asset / n` always
+    /// rounds toward zero.
+    pub fn div_rounded(self, rhs: i128, rounding: RoundingMode) -> Result<Self, Error> {
+        self.div_decimal((rhs, 0), rounding)
+    }
+
+    /// Multiply the asset for `num` and divide it for `den` rounding once with `rounding`, the
+    /// pro-rata of fees, shares and exchanges: `fee.mul_div(shares, total_shares, HalfEven)`.
+    ///
+    /// The product is computed in a double-width integer, so it can exceed the bound of the asset
+    /// (and even the range of i128) as long as the final result is in bound.
+    pub fn mul_div(self, num: i128, den: i128, rounding: RoundingMode) -> Result<Self, Error> {
+        let raw = mul_div_rounded(
+            self.get_inner().to_raw(),
+            num,
+            den,
+            rounding,
+            T::TICK as u128,
+        )?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+
+    /// Round the asset to `decimals` decimal digits with `rounding`: 1.2345 rounded to 2 decimal
+    /// digits is 1.23 with `HalfEven`. If the asset has `decimals` or less decimal digits the
+    /// value is returned unchanged.
+    ///
+    /// For an asset with a tick the result is a multiple of both the tick and `10^-decimals`.
+    pub fn round_to(self, decimals: u32, rounding: RoundingMode) -> Result<Self, Error> {
+        if decimals >= T::DECIMALS {
+            return Ok(self);
+        }
+        let pow = 10_i128.pow(T::DECIMALS - decimals);
+        let unit = lcm(pow, T::TICK).ok_or(Error::Overflow)?;
+        let rounded = div_decimal_rounded(self.get_inner().to_raw(), (unit, 0), rounding, 1)?;
+        let raw = rounded.checked_mul(unit).ok_or(Error::Overflow)?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+}
+
+// Asset / (n, rounding) -> Asset?
+impl<T: CheckedOps> ops::Div<(i128, RoundingMode)> for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn div(self, rhs: (i128, RoundingMode)) -> Self::Output {
+        self.div_rounded(rhs.0, rhs.1)
+    }
+}
+
+// Asset * (mantissa, decimal part, rounding) -> Asset?
+impl<T: CheckedOps> ops::Mul<(i128, u32, RoundingMode)> for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn mul(self, rhs: (i128, u32, RoundingMode)) -> Self::Output {
+        self.mul_decimal((rhs.0, rhs.1), rhs.2)
+    }
+}
+
+// Asset / (mantissa, decimal part, rounding) -> Asset?
+impl<T: CheckedOps> ops::Div<(i128, u32, RoundingMode)> for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn div(self, rhs: (i128, u32, RoundingMode)) -> Self::Output {
+        self.div_decimal((rhs.0, rhs.1), rhs.2)
+    }
+}
+
+// The float is taken as the shortest decimal that represents it, so 1.075 is exactly 1.075 and
+// not 1.07499999999999995559107901499...
+//
+// A factor too big to be a `(mantissa, decimal part)` tuple is bigger than any raw value, so the
+// product is out of bound unless the asset is 0 and the quotient is less than a minimum unit.
+impl<T: CheckedOps> ops::Mul<(f64, RoundingMode)> for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn mul(self, rhs: (f64, RoundingMode)) -> Self::Output {
+        let raw = self.inner().to_raw();
+        let product = match float_to_decimal(rhs.0) {
+            Err(Error::Overflow) if raw == 0 => return Ok(self),
+            Err(Error::Overflow) => Err(Error::Overflow),
+            factor => self.mul_decimal(factor?, rhs.1),
+        };
+        product.map_err(|error| match error {
+            Error::Overflow => out_of_bound((raw < 0) != (rhs.0 < 0.0), T::MIN, T::MAX),
+            error => error,
+        })
+    }
+}
+
+impl<T: CheckedOps> ops::Div<(f64, RoundingMode)> for Asset<T> {
+    type Output = Result<Asset<T>, Error>;
+
+    #[inline]
+    fn div(self, rhs: (f64, RoundingMode)) -> Self::Output {
+        match float_to_decimal(rhs.0) {
+            Err(Error::Overflow) => self.div_huge(rhs.0, rhs.1),
+            factor => self.div_decimal(factor?, rhs.1),
+        }
+    }
+}
+
+impl<T: CheckedOps> Asset<T> {
+    /// Divide for a float bigger than 2^127, the quotient is less than one minimum unit and it is
+    /// rounded comparing twice the raw value with the factor
+    fn div_huge(self, factor: f64, rounding: RoundingMode) -> Result<Self, Error> {
+        let raw = self.get_inner().to_raw();
+        let (mantissa, exponent) = float_to_parts(factor)?;
+        let twice = U256::mul_u128(raw.wrapping_abs() as u128, 2);
+        // the exponent is positive, with more than 38 digits the factor is above 2^128
+        let fraction = match 10_u128.checked_pow(exponent as u32) {
+            _ if raw == 0 => Fraction::Zero,
+            None => Fraction::BelowHalf,
+            Some(pow) => {
+                let divisor = U256::mul_u128(mantissa.wrapping_abs() as u128, pow);
+                match (twice.hi, twice.lo).cmp(&(divisor.hi, divisor.lo)) {
+                    Ordering::Less => Fraction::BelowHalf,
+                    Ordering::Equal => Fraction::Half,
+                    Ordering::Greater => Fraction::AboveHalf,
+                }
+            }
+        };
+        let negative = (raw < 0) != (factor < 0.0);
+        let raw = round_quotient(U256::ZERO, negative, fraction, rounding, T::TICK as u128)?;
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+}
@@ -0,0 +1,169 @@
+use super::asset::{Asset, CheckedOps};
+use crate::error::Error;
+use crate::rounding::{Fraction, RoundingMode};
+use crate::utils::wide::{mul_pow_10_rounded, round_quotient, U256};
+
+/// Decimal digits kept by the intermediate values of `compound_interest` with `RoundAt::End` and
+/// of `continuous_interest`
+const GUARD_DIGITS: u32 = 18;
+
+/// When the rounding mode is applied to a compound interest
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RoundAt {
+    /// The balance is rounded to the precision of the asset at the end of every period, like a
+    /// bank account that pays the interest every period.
+    EachPeriod,
+    /// The balance keeps 18 more decimal digits than the asset and is rounded only at the end.
+    End,
+}
+
+impl<T: CheckedOps> Asset<T> {
+    /// Interest of the asset after `periods` periods at `rate` per period, with the rate expressed
+    /// as a `(mantissa, decimal part)` tuple: `(5, 2)` is 5%.
+    ///
+    /// The interest is `amount * rate * periods` rounded once with `rounding`. A negative rate or
+    /// a `Debt` give a negative interest.
+    pub fn simple_interest(
+        self,
+        rate: (i128, u32),
+        periods: u32,
+        rounding: RoundingMode,
+    ) -> Result<Asset<T>, Error> {
+        let raw = self.get_inner().to_raw();
+        let tick = T::TICK as u128;
+        let negative = (raw < 0) != (rate.0 < 0);
+        let product = U256::mul_u128(raw.wrapping_abs() as u128, rate.0.wrapping_abs() as u128)
+            .checked_mul_u128(periods as u128)
+            .ok_or(Error::Overflow)?;
+        let interest = mul_pow_10_rounded(product, negative, -(rate.1 as i64), rounding, tick)?;
+        Ok(Asset::from_inner(T::from_raw_checked(interest)?))
+    }
+
+    /// Interest of the asset after `periods` periods at `rate` per period compounded every period,
+    /// with the rate expressed as a `(mantissa, decimal part)` tuple: `(5, 2)` is 5%.
+    ///
+    /// With `RoundAt::EachPeriod` the balance is rounded with `rounding` and checked against the
+    /// bound of the asset at the end of every period, with `RoundAt::End` only the final balance
+    /// is rounded and checked. The interest is the final balance minus the original amount.
+    pub fn compound_interest(
+        self,
+        rate: (i128, u32),
+        periods: u32,
+        rounding: RoundingMode,
+        round_at: RoundAt,
+    ) -> Result<Asset<T>, Error> {
+        let raw = self.get_inner().to_raw();
+        let tick = T::TICK as u128;
+        // balance * (1 + rate) = balance * (10^scale + mantissa) / 10^scale
+        let one = 10_i128.checked_pow(rate.1).ok_or(Error::Overflow)?;
+        let factor = one.checked_add(rate.0).ok_or(Error::Overflow)?;
+        let factor_negative = factor < 0;
+        let factor = factor.wrapping_abs() as u128;
+        let scale = -(rate.1 as i64);
+
+        let mut negative = raw < 0;
+        let balance = match round_at {
+            RoundAt::EachPeriod => {
+                let mut balance = raw;
+                for _ in 0..periods {
+                    negative = (balance < 0) != factor_negative;
+                    let product = U256::mul_u128(balance.wrapping_abs() as u128, factor);
+                    balance = mul_pow_10_rounded(product, negative, scale, rounding, tick)?;
+                    T::from_raw_checked(balance)?;
+                }
+                balance
+            }
+            RoundAt::End => {
+                let mut balance =
+                    U256::mul_u128(raw.wrapping_abs() as u128, 10_u128.pow(GUARD_DIGITS));
+                for _ in 0..periods {
+                    negative ^= factor_negative;
+                    let product = balance.checked_mul_u128(factor).ok_or(Error::Overflow)?;
+                    balance = product.div_pow_10(rate.1).0;
+                }
+                let (balance, fraction) = balance.div_pow_10(GUARD_DIGITS);
+                round_quotient(balance, negative, fraction, rounding, tick)?
+            }
+        };
+        let interest = balance.checked_sub(raw).ok_or(Error::Overflow)?;
+        Ok(Asset::from_inner(T::from_raw_checked(interest)?))
+    }
+
+    /// Interest of the asset after `periods` periods at `rate` per period compounded
+    /// continuously, that is `amount * e^(rate * periods) - amount`, with the rate expressed as a
+    /// `(mantissa, decimal part)` tuple: `(5, 2)` is 5%.
+    ///
+    /// `e^(rate * periods)` is approximated with 18 decimal digits, the result is rounded once with
+    /// `rounding`. Fail with `Error::Overflow` if `rate * periods` is too big to be approximated,
+    /// a negative rate so big that `e^(rate * periods)` is below `10^-18` gives about `-amount`.
+    pub fn continuous_interest(
+        self,
+        rate: (i128, u32),
+        periods: u32,
+        rounding: RoundingMode,
+    ) -> Result<Asset<T>, Error> {
+        let raw = self.get_inner().to_raw();
+        let tick = T::TICK as u128;
+        let magnitude = raw.wrapping_abs() as u128;
+        let negative = raw < 0;
+        let exponent = U256::mul_u128(rate.0.wrapping_abs() as u128, periods as u128);
+        let exp = mul_pow_10_rounded(
+            exponent,
+            false,
+            GUARD_DIGITS as i64 - rate.1 as i64,
+            RoundingMode::TowardZero,
+            1,
+        )
+        .and_then(|exponent| exp_fixed(exponent as u128));
+        let balance = match exp {
+            Ok(exp) if rate.0 >= 0 => {
+                let product = U256::mul_u128(magnitude, exp);
+                mul_pow_10_rounded(product, negative, -(GUARD_DIGITS as i64), rounding, tick)?
+            }
+            Ok(exp) => {
+                let product = U256::mul_u128(magnitude, 10_u128.pow(GUARD_DIGITS));
+                let (quotient, remainder) = product.div_rem_u128(exp);
+                let fraction = Fraction::from_remainder(remainder, exp);
+                round_quotient(quotient, negative, fraction, rounding, tick)?
+            }
+            // e^(rate * periods) underflows to 0 with 18 decimal digits, the balance is a
+            // positive value smaller than the precision of the approximation
+            Err(Error::Overflow) if rate.0 < 0 => {
+                let fraction = if magnitude == 0 {
+                    Fraction::Zero
+                } else {
+                    Fraction::BelowHalf
+                };
+                round_quotient(U256::ZERO, negative, fraction, rounding, tick)?
+            }
+            Err(error) => return Err(error),
+        };
+        let interest = balance.checked_sub(raw).ok_or(Error::Overflow)?;
+        Ok(Asset::from_inner(T::from_raw_checked(interest)?))
+    }
+}
+
+/// e^x where x and the result have `GUARD_DIGITS` decimal digits
+fn exp_fixed(x: u128) -> Result<u128, Error> {
+    let one = 10_u128.pow(GUARD_DIGITS);
+    // e^x = (e^(x / 2^k))^(2^k) with x / 2^k <= 0.5 so that the series converges quickly
+    let mut k = 0;
+    while (x >> k) > one / 2 {
+        k += 1;
+    }
+    let y = x >> k;
+    let mut sum = one;
+    let mut term = one;
+    let mut i = 1;
+    while term != 0 {
+        let (next, _) = U256::mul_u128(term, y).div_pow_10(GUARD_DIGITS);
+        term = next.lo / i;
+        sum += term;
+        i += 1;
+    }
+    for _ in 0..k {
+        let (square, _) = U256::mul_u128(sum, sum).div_pow_10(GUARD_DIGITS);
+        sum = square.to_u128().ok_or(Error::Overflow)?;
+    }
+    Ok(sum)
+}
@@ -1,7 +1,20 @@
 #[macro_use]
+#[allow(clippy::module_inception)]
 pub mod asset;
 
+pub mod allocation;
+pub mod dynamic;
+pub mod factor;
+pub mod interest;
+mod primitive;
+pub mod rate;
+pub mod saturating;
+
 #[cfg(test)]
 pub mod test;
 
 pub use asset::*;
+pub use dynamic::{AssetSpec, DynAsset};
+pub use factor::FloatConversion;
+pub use interest::RoundAt;
+pub use rate::{convert, Rate};
@@ -0,0 +1,79 @@
+use super::asset::{AssetInfo, CheckedOps, NonNegative};
+use crate::error::Error;
+use std::convert::TryFrom;
+
+/// The error for a value out of the range of a primitive: `Error::WouldGoNegative` for the
+/// unsigned integers and `Error::Overflow` otherwise, the range of a primitive is its storage.
+#[inline(always)]
+fn out_of_range(negative: bool, min: i128) -> Error {
+    if negative && min == 0 {
+        Error::WouldGoNegative
+    } else {
+        Error::Overflow
+    }
+}
+
+// A primitive integer is an asset without decimal digits bounded by its range, so `Asset<i64>`
+// and `Asset<u32>` can be used to count things without defining an asset.
+macro_rules! impl_primitive {
+    ($($ty_:ident),*) => {
+        $(
+            impl AssetInfo for $ty_ {
+                const NAME: &'static str = stringify!($ty_);
+                const DECIMALS: u32 = 0;
+                const MAX: i128 = $ty_::MAX as i128;
+                const MIN: i128 = $ty_::MIN as i128;
+                const STORAGE_BYTES: usize = std::mem::size_of::<$ty_>();
+            }
+
+            impl CheckedOps for $ty_ {
+                #[inline]
+                fn add_checked(self, rhs: Self) -> Result<Self, Error> {
+                    let raw = self.to_raw().checked_add(rhs.to_raw());
+                    Self::from_raw_checked(raw.ok_or(Error::Overflow)?)
+                }
+                #[inline]
+                fn sub_checked(self, rhs: Self) -> Result<Self, Error> {
+                    let raw = self.to_raw().checked_sub(rhs.to_raw());
+                    Self::from_raw_checked(raw.ok_or(Error::Overflow)?)
+                }
+                #[inline]
+                fn neg_checked(self) -> Result<Self, Error> {
+                    Self::from_raw_checked(self.to_raw().checked_neg().ok_or(Error::Overflow)?)
+                }
+                #[inline]
+                fn mul_checked(self, rhs: i128) -> Result<Self, Error> {
+                    Self::from_raw_checked(self.to_raw().checked_mul(rhs).ok_or(Error::Overflow)?)
+                }
+                #[inline]
+                fn div_checked(self, rhs: i128) -> Result<Self, Error> {
+                    if rhs == 0 {
+                        return Err(Error::DivisionByZero);
+                    }
+                    Self::from_raw_checked(self.to_raw().checked_div(rhs).ok_or(Error::Overflow)?)
+                }
+                #[inline]
+                fn is_positive(&self) -> bool {
+                    self.to_raw() >= 0
+                }
+                #[inline]
+                fn to_raw(&self) -> i128 {
+                    *self as i128
+                }
+                #[inline]
+                fn from_raw_checked(raw: i128) -> Result<Self, Error> {
+                    let min = <Self as AssetInfo>::MIN;
+                    $ty_::try_from(raw).map_err(|_| out_of_range(raw < 0, min))
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl NonNegative for u8 {}
+impl NonNegative for u16 {}
+impl NonNegative for u32 {}
+impl NonNegative for u64 {}
+impl NonNegative for usize {}
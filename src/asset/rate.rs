@@ -0,0 +1,104 @@
+use super::asset::{Asset, CheckedOps};
+use crate::error::Error;
+use crate::rounding::RoundingMode;
+use crate::utils::parse::parse_decimal;
+use crate::utils::wide::{mul_pow_10_rounded, U256};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+/// An exchange rate: how many units of the asset `T` are worth one unit of the asset `F`.
+///
+/// The rate is an exact decimal `mantissa * 10^-scale` and can not be negative, so a conversion
+/// never turns a `Credit` into a `Debt` or vice versa.
+///
+/// ```
+/// #[macro_use]
+/// extern crate merx;
+/// use merx::asset::{convert, Rate};
+/// use merx::{Asset, RoundingMode};
+/// use std::convert::TryFrom;
+///
+/// new_asset!(bitcoin, 8, 21_000_000);
+/// new_asset!(usd, 2, 14_000_000_000_000);
+///
+/// fn main() {
+///     let btc = Asset::<bitcoin::Value>::try_from("0.5").unwrap();
+///     let rate = Rate::<bitcoin::Value, usd::Value>::try_from("6543.215").unwrap();
+///     let usd = convert(btc, rate, RoundingMode::HalfAwayFromZero).unwrap();
+///     assert_eq!(usd.to_string(), "3271.61");
+/// }
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct Rate<F, T> {
+    mantissa: u128,
+    scale: u32,
+    assets: PhantomData<(F, T)>,
+}
+
+impl<F, T> Clone for Rate<F, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F, T> Copy for Rate<F, T> {}
+
+impl<F, T> Rate<F, T> {
+    /// A rate of `mantissa * 10^-scale` units of `T` for one unit of `F`
+    pub fn new(mantissa: u128, scale: u32) -> Self {
+        Rate {
+            mantissa,
+            scale,
+            assets: PhantomData,
+        }
+    }
+
+    /// Return the rate as a `(mantissa, scale)` tuple
+    pub fn to_parts(self) -> (u128, u32) {
+        (self.mantissa, self.scale)
+    }
+}
+
+impl<F, T> TryFrom<&str> for Rate<F, T> {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (mantissa, scale) = parse_decimal(value)?;
+        if mantissa < 0 {
+            return Err(Error::Parse {
+                position: 0,
+                reason: "a rate can not be negative",
+            });
+        }
+        Ok(Rate::new(mantissa as u128, scale))
+    }
+}
+
+/// Convert an amount of `F` in an amount of `T`.
+///
+/// The product is computed exactly and rounded once with `rounding` to the precision of `T`, then
+/// checked against the bound of `T`. A `Debt` becomes a `Debt` and a `Credit` a `Credit`, unless
+/// the result is rounded to zero that is always a `Credit`.
+pub fn convert<F: CheckedOps, T: CheckedOps>(
+    asset: Asset<F>,
+    rate: Rate<F, T>,
+    rounding: RoundingMode,
+) -> Result<Asset<T>, Error> {
+    let raw = asset.get_inner().to_raw();
+    let negative = raw < 0;
+    let product = U256::mul_u128(raw.wrapping_abs() as u128, rate.mantissa);
+    let exp = T::DECIMALS as i64 - F::DECIMALS as i64 - rate.scale as i64;
+    let raw = mul_pow_10_rounded(product, negative, exp, rounding, T::TICK as u128)?;
+    Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+}
+
+impl<F: CheckedOps> Asset<F> {
+    /// Convert the asset with an exchange rate, see [`convert`](fn.convert.html)
+    pub fn convert<T: CheckedOps>(
+        self,
+        rate: Rate<F, T>,
+        rounding: RoundingMode,
+    ) -> Result<Asset<T>, Error> {
+        convert(self, rate, rounding)
+    }
+}
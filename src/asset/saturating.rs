@@ -0,0 +1,144 @@
+use super::asset::{Asset, CheckedOps, Credit, Debt};
+
+/// Saturating and overflowing arithmetic, for the places where a value clamped to the bound of
+/// the asset is better than an error, like risk-limit counters or previews.
+///
+/// An operation that would exceed `MAX` returns the greatest value of the asset (a multiple of
+/// its tick) and one that would go below `MIN` the smallest one. The `overflowing_*` methods
+/// return the clamped value and `true` if it has been clamped.
+///
+/// ```
+/// # #[macro_use] extern crate merx;
+/// use merx::Asset;
+/// use std::convert::TryFrom;
+///
+/// new_asset!(usd, 2, 1_000);
+///
+/// # fn main() {
+/// let limit = Asset::<usd::Value>::try_from(900).unwrap();
+/// let order = Asset::<usd::Value>::try_from(200).unwrap();
+/// assert_eq!(limit.saturating_add(order).to_string(), "1000.00");
+/// assert_eq!(limit.overflowing_mul(-2), (Asset::try_from(-1_000).unwrap(), true));
+/// # }
+/// ```
+impl<T: CheckedOps> Asset<T> {
+    #[inline]
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        with_asset(self.get_inner().overflowing_add(rhs.get_inner()))
+    }
+
+    #[inline]
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        with_asset(self.get_inner().overflowing_sub(rhs.get_inner()))
+    }
+
+    #[inline]
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        with_asset(self.get_inner().overflowing_neg())
+    }
+
+    #[inline]
+    pub fn overflowing_mul(self, rhs: i128) -> (Self, bool) {
+        with_asset(self.get_inner().overflowing_mul(rhs))
+    }
+
+    /// Divide for an integer like `Asset / i128` and clamp the result, panic if `rhs` is 0
+    #[inline]
+    pub fn overflowing_div(self, rhs: i128) -> (Self, bool) {
+        with_asset(self.get_inner().overflowing_div(rhs))
+    }
+
+    #[inline]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    #[inline]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    #[inline]
+    pub fn saturating_neg(self) -> Self {
+        self.overflowing_neg().0
+    }
+
+    #[inline]
+    pub fn saturating_mul(self, rhs: i128) -> Self {
+        self.overflowing_mul(rhs).0
+    }
+
+    /// Divide for an integer like `Asset / i128` and clamp the result, panic if `rhs` is 0
+    #[inline]
+    pub fn saturating_div(self, rhs: i128) -> Self {
+        self.overflowing_div(rhs).0
+    }
+}
+
+#[inline]
+fn with_asset<T: CheckedOps>(result: (T, bool)) -> (Asset<T>, bool) {
+    (Asset::from_inner(result.0), result.1)
+}
+
+// Credit + Credit and Credit - Debt are always a Credit, so they saturate to MAX
+impl<T: CheckedOps> Credit<T> {
+    #[inline]
+    pub fn overflowing_add(self, rhs: Credit<T>) -> (Credit<T>, bool) {
+        let (value, clamped) = self.0.overflowing_add(rhs.0);
+        (Credit(value), clamped)
+    }
+
+    #[inline]
+    pub fn overflowing_sub(self, rhs: Debt<T>) -> (Credit<T>, bool) {
+        let (value, clamped) = self.0.overflowing_sub(rhs.0);
+        (Credit(value), clamped)
+    }
+
+    #[inline]
+    pub fn saturating_add(self, rhs: Credit<T>) -> Credit<T> {
+        self.overflowing_add(rhs).0
+    }
+
+    #[inline]
+    pub fn saturating_sub(self, rhs: Debt<T>) -> Credit<T> {
+        self.overflowing_sub(rhs).0
+    }
+}
+
+// Debt + Debt and Debt - Credit are always a Debt, so they saturate to MIN
+impl<T: CheckedOps> Debt<T> {
+    #[inline]
+    pub fn overflowing_add(self, rhs: Debt<T>) -> (Debt<T>, bool) {
+        let start = self.0.to_raw();
+        clamp_debt(start, self.0.overflowing_add(rhs.0))
+    }
+
+    #[inline]
+    pub fn overflowing_sub(self, rhs: Credit<T>) -> (Debt<T>, bool) {
+        let start = self.0.to_raw();
+        clamp_debt(start, self.0.overflowing_sub(rhs.0))
+    }
+
+    #[inline]
+    pub fn saturating_add(self, rhs: Debt<T>) -> Debt<T> {
+        self.overflowing_add(rhs).0
+    }
+
+    #[inline]
+    pub fn saturating_sub(self, rhs: Credit<T>) -> Debt<T> {
+        self.overflowing_sub(rhs).0
+    }
+}
+
+// The result is never bigger than the debt it starts from, so if MIN rounded to the tick is not
+// below 0 that debt is the smallest one of the asset and it is returned instead of a `Debt(0)`
+#[inline]
+fn clamp_debt<T: CheckedOps>(start: i128, result: (T, bool)) -> (Debt<T>, bool) {
+    match result {
+        (value, clamped) if !value.is_positive() => (Debt(value), clamped),
+        _ => {
+            let value = T::from_raw_checked(start).expect("a debt is in the bound of its asset");
+            (Debt(value), true)
+        }
+    }
+}
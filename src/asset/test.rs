@@ -1,24 +1,38 @@
 // Props:
 // 1. asset of the same kind can be summed and subtracted
 // 2. asset of differnts kind can not be summed or subtracted
-// 3. neither assets of the same or different kind can be multiplied or divided
+// 3. neither assets of the same or different kind can be multiplied or divided, assets can be
+//    multiplied or divided for integers and decimal factors
 // 4. build an asset for an amount bigger than upper buond return error
 // 5. negative amunts returns Debt<Asset> positive Credit<Asset>
-// 6. TODO assets with no upper bound have an upper bound of i128::max_value - frac part
+// 6. assets with no upper bound are limited only by the range of i128
 // 7. assets with upper bound have an inner value of fewer bits possible            ###!
 // 8. define an asset that can no be represented by an i128 result in a panic
-// 9. overflowing operations on Assets result in None
-// 8. TODO exchange rates
-// 10. TODO operator overloading for `== <= >= !=`                                       ###!
-use super::Asset;
+// 9. overflowing operations on Assets result in an Error
+// 8. exchange rates keep the sign and respect the bound of the target asset
+// 10. assets are compared, ordered and hashed by their numeric value
+use super::{
+    convert, get_inner_len, Asset, AssetInfo, CheckedOps, Credit, NonNegative, Rate, RoundAt,
+};
+use crate::fixed::FixedToInt;
+use crate::{Error, RoundingMode};
 use quickcheck::{quickcheck, TestResult};
+use std::convert::TryFrom;
 
-get_traits!();
 new_asset!(test_asset1, 10, 10_000_000_000);
 new_asset!(test_asset_low_precision, 2, 18446744073709551615);
 new_asset!(test_asset_pass_with_i32, 1, 18446744073709551615);
 new_asset!(test_asset_with_upper, 6, 1234);
 new_asset!(test_asset_overflow, 0, 2147483647);
+new_asset!(test_asset_unbounded, 2);
+new_asset!(test_asset_unbounded_units, 0, unbounded);
+new_asset!(test_asset_tick, 2, 1_000_000, tick = 5);
+new_asset!(test_asset_wallet, 2, min = -500, max = 1_000_000);
+new_asset!(test_asset_only_positive, 2, min = 0, max = 1000);
+new_asset!(test_asset_non_negative, 0, 1_000_000, non_negative);
+new_asset!(test_asset_non_negative_unbounded, 8, non_negative);
+new_asset!(test_asset_i64_storage, 0, 9_223_372_036_854_775_807);
+new_asset!(test_asset_i64_bounded, 4, 100_000_000_000_000);
 
 #[quickcheck]
 fn prop_add_same_kind_assets(amount1: i128, amount2: i128) -> TestResult {
@@ -27,8 +41,8 @@ fn prop_add_same_kind_assets(amount1: i128, amount2: i128) -> TestResult {
     let asset2 = MyAsset::try_from(amount2);
     match (asset1, asset2) {
         (Ok(asset1), Ok(asset2)) => match asset1 + asset2 {
-            None => TestResult::discard(),
-            Some(asset) => {
+            Err(_) => TestResult::discard(),
+            Ok(asset) => {
                 let expected = amount1 + amount2;
                 TestResult::from_bool(expected == asset.to_int())
             }
@@ -45,7 +59,7 @@ fn prop_mul_asset_with_num(amount1: i128, operator: i128) -> TestResult {
         Ok(asset1) => {
             let asset2 = asset1 * operator;
             match asset2 {
-                Some(asset2) => {
+                Ok(asset2) => {
                     let expected = amount1 * operator;
                     TestResult::from_bool(expected == asset2.to_int())
                 }
@@ -63,8 +77,8 @@ fn prop_add_same_kind_assets_frac(amount1: (i128, i128), amount2: (i128, i128))
     let asset2 = MyAsset::try_from(amount2);
     match (asset1, asset2) {
         (Ok(asset1), Ok(asset2)) => match asset1 + asset2 {
-            None => TestResult::discard(),
-            Some(asset) => {
+            Err(_) => TestResult::discard(),
+            Ok(asset) => {
                 let asset1 = amount1.0 * 10_i128.pow((10 - amount1.1) as u32);
                 let asset2 = amount2.0 * 10_i128.pow((10 - amount2.1) as u32);
                 let expected = asset1 + asset2;
@@ -159,10 +173,10 @@ fn prop_error_on_overflow1(amount: i32, operator: i32) -> TestResult {
     type MyAsset = Asset<test_asset_overflow::Value>;
     let asset = MyAsset::try_from(amount as i128).unwrap();
     let mul = asset * operator as i128;
-    if amount.checked_mul(operator) == None {
+    if amount.checked_mul(operator).is_none() {
         match mul {
-            None => TestResult::from_bool(true),
-            Some(_) => TestResult::from_bool(false),
+            Err(_) => TestResult::from_bool(true),
+            Ok(_) => TestResult::from_bool(false),
         }
     } else {
         TestResult::discard()
@@ -174,20 +188,1709 @@ fn prop_error_on_overflow2(amount: i32, operator: i32) -> TestResult {
     type MyAsset = Asset<test_asset_overflow::Value>;
     let asset = MyAsset::try_from(amount as i128).unwrap();
     let mul = asset / operator as i128;
-    if amount.checked_div(operator) == None {
+    if amount.checked_div(operator).is_none() {
         match mul {
-            None => TestResult::from_bool(true),
-            Some(_) => TestResult::from_bool(false),
+            Err(_) => TestResult::from_bool(true),
+            Ok(_) => TestResult::from_bool(false),
         }
     } else {
         TestResult::discard()
     }
 }
 
+#[quickcheck]
+fn prop_sub_same_kind_assets(amount1: i32, amount2: i32) -> TestResult {
+    type MyAsset = Asset<test_asset_overflow::Value>;
+    let (asset1, asset2) = match (
+        MyAsset::try_from(amount1 as i128),
+        MyAsset::try_from(amount2 as i128),
+    ) {
+        (Ok(asset1), Ok(asset2)) => (asset1, asset2),
+        _ => return TestResult::discard(),
+    };
+    let sub = asset1 - asset2;
+    match (amount1.checked_sub(amount2), sub) {
+        (None, Err(_)) => TestResult::passed(),
+        (Some(expected), Ok(sub)) => TestResult::from_bool(
+            expected as i128 == sub.to_int() && sub.get_inner().is_positive() == (expected >= 0),
+        ),
+        _ => TestResult::failed(),
+    }
+}
+
+#[quickcheck]
+fn prop_neg_and_abs_keep_the_sign(amount: i32) -> TestResult {
+    type MyAsset = Asset<test_asset_overflow::Value>;
+    let asset = match MyAsset::try_from(amount as i128) {
+        Ok(asset) => asset,
+        Err(_) => return TestResult::discard(),
+    };
+    match (amount.checked_neg(), -asset, asset.abs()) {
+        (None, Err(_), Err(_)) => TestResult::passed(),
+        (Some(expected), Ok(neg), Ok(abs)) => TestResult::from_bool(
+            expected as i128 == neg.to_int()
+                && amount.abs() as i128 == Asset::Credit(abs).to_int()
+                && (neg + asset).map(|zero| zero.to_int()) == Ok(0),
+        ),
+        _ => TestResult::failed(),
+    }
+}
+
+#[test]
+fn sign_aware_ops() {
+    type MyAsset = Asset<test_asset_with_upper::Value>;
+    let (credit, debt) = match (MyAsset::try_from(6), MyAsset::try_from(-4)) {
+        (Ok(Asset::Credit(credit)), Ok(Asset::Debt(debt))) => (credit, debt),
+        _ => panic!("wrong kind"),
+    };
+    assert_eq!(Asset::Credit((credit - debt).unwrap()).to_int(), 10);
+    assert_eq!(Asset::Debt((debt - credit).unwrap()).to_int(), -10);
+    assert_eq!((credit + debt).unwrap().to_int(), 2);
+    assert_eq!((debt - debt).unwrap().to_int(), 0);
+    assert_eq!((credit - credit).unwrap().to_int(), 0);
+    assert_eq!(Asset::Debt((-credit).unwrap()).to_int(), -6);
+    assert_eq!(Asset::Credit((-debt).unwrap()).to_int(), 4);
+
+    let max = 1234 * 10_i128.pow(6);
+    let big = MyAsset::try_from(1000).unwrap();
+    let small = MyAsset::try_from(-1000).unwrap();
+    assert_eq!(big - small, Err(Error::AboveBound { max }));
+
+    // The bound of an asset stored in an i32 is symmetric so -MAX can always be negated, but the
+    // difference of two values can overflow the storage before it is checked against the bound.
+    type MyAsset32 = Asset<test_asset_overflow::Value>;
+    let min = MyAsset32::try_from(-2147483647).unwrap();
+    let max = MyAsset32::try_from(2147483647).unwrap();
+    assert_eq!((-min).unwrap(), max);
+    assert_eq!(Asset::Credit(min.abs().unwrap()), max);
+    assert_eq!(min - max, Err(Error::Overflow));
+}
+
+#[quickcheck]
+fn prop_assets_are_ordered_by_value(amount1: i64, amount2: i64) -> TestResult {
+    type MyAsset = Asset<test_asset_pass_with_i32::Value>;
+    let (asset1, asset2) = match (
+        MyAsset::try_from((amount1 as i128, 1)),
+        MyAsset::try_from((amount2 as i128, 1)),
+    ) {
+        (Ok(asset1), Ok(asset2)) => (asset1, asset2),
+        _ => return TestResult::discard(),
+    };
+    TestResult::from_bool(
+        asset1.cmp(&asset2) == amount1.cmp(&amount2) && (asset1 == asset2) == (amount1 == amount2),
+    )
+}
+
+#[test]
+fn assets_can_be_keys() {
+    use std::collections::{BTreeMap, HashSet};
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let amounts = ["-1.5", "0", "2.25", "-1.50", "10"];
+    let assets: Vec<MyAsset> = amounts.iter().map(|a| a.parse().unwrap()).collect();
+
+    let set: HashSet<MyAsset> = assets.iter().cloned().collect();
+    assert_eq!(set.len(), 4);
+
+    let map: BTreeMap<MyAsset, usize> = assets.iter().cloned().zip(0..).collect();
+    let sorted: Vec<String> = map.keys().map(|a| a.to_string()).collect();
+    assert_eq!(sorted, vec!["-1.50", "0.00", "2.25", "10.00"]);
+
+    let (credit, debt) = match (assets[2], assets[0]) {
+        (Asset::Credit(credit), Asset::Debt(debt)) => (credit, debt),
+        _ => panic!("wrong kind"),
+    };
+    assert!(credit > debt);
+    assert!(debt < credit);
+    assert!(credit != debt);
+    // A `Debt` is never 0, the opposite of `Credit(0)` is only an `Asset`
+    let zero = match assets[1] {
+        Asset::Credit(zero) => zero,
+        _ => panic!("wrong kind"),
+    };
+    assert_eq!(-zero, Err(Error::WrongSign));
+    assert_eq!((-Asset::Credit(zero)).unwrap(), Asset::Credit(zero));
+}
+
+#[quickcheck]
+fn prop_unbounded_assets_overflow_like_i128(amount1: i128, amount2: i128) -> TestResult {
+    type MyAsset = Asset<test_asset_unbounded_units::Value>;
+    let asset1 = MyAsset::try_from(amount1).unwrap();
+    let asset2 = MyAsset::try_from(amount2).unwrap();
+    let sum = (asset1 + asset2).map(|sum| sum.to_int());
+    let sub = (asset1 - asset2).map(|sub| sub.to_int());
+    TestResult::from_bool(
+        sum.ok() == amount1.checked_add(amount2) && sub.ok() == amount1.checked_sub(amount2),
+    )
+}
+
+#[test]
+fn unbounded_assets() {
+    type Units = Asset<test_asset_unbounded_units::Value>;
+    let min = Units::try_from(i128::MIN).unwrap();
+    let max = Units::try_from(i128::MAX).unwrap();
+    assert_eq!(min.to_int(), i128::MIN);
+    assert_eq!((max + Units::try_from(1).unwrap()), Err(Error::Overflow));
+    assert_eq!(-min, Err(Error::Overflow));
+    assert_eq!(min.abs(), Err(Error::Overflow));
+    assert_eq!(
+        (-max).unwrap(),
+        (min + Units::try_from(1).unwrap()).unwrap()
+    );
+
+    type Points = Asset<test_asset_unbounded::Value>;
+    let big = Points::try_from(i128::MAX / 100).unwrap();
+    assert_eq!(Points::try_from(i128::MAX / 100 + 1), Err(Error::Overflow));
+    assert_eq!(big * 2, Err(Error::Overflow));
+    assert_eq!(
+        "1701411834604692317316873037158841057.27"
+            .parse::<Points>()
+            .unwrap()
+            .get_inner()
+            .to_raw(),
+        i128::MAX
+    );
+    assert_eq!(
+        Points::try_from((1e40, RoundingMode::HalfEven)),
+        Err(Error::Overflow)
+    );
+}
+
+#[quickcheck]
+fn prop_tick_rounding_is_correct(mantissa: i64, scale: u32) -> TestResult {
+    type MyAsset = Asset<test_asset_tick::Value>;
+    let scale = scale % 7;
+    // value in ticks of 0.05 is mantissa * 10^-scale / 0.05
+    let num = mantissa as i128 * 100;
+    let den = 5 * 10_i128.pow(scale);
+    let (quotient, remainder) = (num / den, (num % den).abs());
+    let expected = if 2 * remainder > den || (2 * remainder == den && quotient % 2 != 0) {
+        quotient + num.signum()
+    } else {
+        quotient
+    };
+    match MyAsset::from_decimal_rounded((mantissa as i128, scale), RoundingMode::HalfEven) {
+        Ok(asset) => TestResult::from_bool(asset.get_inner().to_raw() == expected * 5),
+        Err(_) => TestResult::discard(),
+    }
+}
+
+#[test]
+fn tick_assets() {
+    type MyAsset = Asset<test_asset_tick::Value>;
+    let not_multiple = Error::NotMultipleOfTick { tick: 5 };
+    assert_eq!("1.05".parse::<MyAsset>().unwrap().to_string(), "1.05");
+    assert_eq!("1.03".parse::<MyAsset>(), Err(not_multiple));
+    assert_eq!(MyAsset::try_from((103, 2)), Err(not_multiple));
+    assert!(MyAsset::try_from(7).is_ok());
+
+    let rounded = |value: &str, mode| MyAsset::parse_rounded(value, mode).unwrap().to_string();
+    assert_eq!(rounded("1.03", RoundingMode::HalfEven), "1.05");
+    assert_eq!(rounded("1.025", RoundingMode::HalfEven), "1.00");
+    assert_eq!(rounded("1.025", RoundingMode::HalfUp), "1.05");
+    assert_eq!(rounded("1.0250001", RoundingMode::HalfEven), "1.05");
+    assert_eq!(rounded("-1.01", RoundingMode::Floor), "-1.05");
+    assert_eq!(
+        MyAsset::parse_rounded("1.01", RoundingMode::Unnecessary),
+        Err(Error::Inexact)
+    );
+    let float = MyAsset::try_from((1.025, RoundingMode::HalfEven)).unwrap();
+    assert_eq!(float.to_string(), "1.00");
+
+    let one: MyAsset = "1".parse().unwrap();
+    assert_eq!((one / 3).unwrap().to_string(), "0.30");
+    assert_eq!(
+        (one / (3, RoundingMode::HalfEven)).unwrap().to_string(),
+        "0.35"
+    );
+    assert_eq!(
+        (one * (15, 1, RoundingMode::HalfEven)).unwrap().to_string(),
+        "1.50"
+    );
+    let parts: Vec<String> = one
+        .allocate(&[1, 1, 1])
+        .unwrap()
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    assert_eq!(parts, vec!["0.35", "0.35", "0.30"]);
+    let parts: Vec<String> = one
+        .split_even(3)
+        .unwrap()
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    assert_eq!(parts, vec!["0.35", "0.35", "0.30"]);
+    let value: MyAsset = "1.15".parse().unwrap();
+    assert_eq!(
+        value
+            .round_to(1, RoundingMode::HalfEven)
+            .unwrap()
+            .to_string(),
+        "1.20"
+    );
+}
+
+#[quickcheck]
+fn prop_asymmetric_bounds(amount1: i32, amount2: i32) -> TestResult {
+    type MyAsset = Asset<test_asset_wallet::Value>;
+    let (min, max) = (-50_000, 100_000_000);
+    let (raw1, raw2) = (amount1 as i128, amount2 as i128);
+    let (asset1, asset2) = match (MyAsset::try_from((raw1, 2)), MyAsset::try_from((raw2, 2))) {
+        (Ok(asset1), Ok(asset2)) => (asset1, asset2),
+        (Err(Error::BelowBound { .. }), _) => return TestResult::from_bool(raw1 < min),
+        (Err(Error::AboveBound { .. }), _) => return TestResult::from_bool(raw1 > max),
+        _ => return TestResult::discard(),
+    };
+    let expected = |raw: i128| {
+        if raw < min {
+            Err(Error::BelowBound { min })
+        } else if raw > max {
+            Err(Error::AboveBound { max })
+        } else {
+            Ok(raw)
+        }
+    };
+    let raw = |asset: MyAsset| asset.get_inner().to_raw();
+    TestResult::from_bool(
+        (asset1 + asset2).map(raw) == expected(raw1 + raw2)
+            && (asset1 - asset2).map(raw) == expected(raw1 - raw2)
+            && (-asset1).map(raw) == expected(-raw1)
+            && (asset1 * 3).map(raw) == expected(raw1 * 3),
+    )
+}
+
+#[test]
+fn asymmetric_bounds() {
+    type Wallet = Asset<test_asset_wallet::Value>;
+    let below = Err(Error::BelowBound { min: -50_000 });
+    let above = Err(Error::AboveBound { max: 100_000_000 });
+    assert!(Wallet::try_from(-500).is_ok());
+    assert_eq!(Wallet::try_from(-501), below);
+    assert_eq!(Wallet::try_from(1_000_001), above);
+    assert_eq!("-500.01".parse::<Wallet>(), below);
+    assert_eq!(
+        Wallet::try_from((-500.005, RoundingMode::HalfAwayFromZero)),
+        below
+    );
+    assert!(Wallet::try_from((-500.004, RoundingMode::HalfEven)).is_ok());
+
+    let credit = Wallet::try_from(100).unwrap();
+    let big = Wallet::try_from(700).unwrap();
+    let debt = Wallet::try_from(-300).unwrap();
+    assert_eq!(credit - big, below);
+    assert_eq!(debt * 2, below);
+    assert_eq!((debt / -1).unwrap(), Wallet::try_from(300).unwrap());
+    assert_eq!(-Wallet::try_from(1_000_000).unwrap(), below);
+    assert_eq!((debt * (2, 0, RoundingMode::HalfEven)), below);
+
+    type OnlyPositive = Asset<test_asset_only_positive::Value>;
+    // an asset with a lower bound of 0 can not go negative
+    let min = Err(Error::WouldGoNegative);
+    assert_eq!(OnlyPositive::try_from(-1), min);
+    let one = OnlyPositive::try_from(1).unwrap();
+    assert_eq!(-one, min);
+    assert_eq!((one - one).unwrap(), OnlyPositive::try_from(0).unwrap());
+    assert_eq!(one - OnlyPositive::try_from(2).unwrap(), min);
+}
+
+#[test]
+fn storage_width_depends_on_both_bounds() {
+    assert_eq!(get_inner_len(-50_000, 100_000_000), 4);
+    assert_eq!(get_inner_len(i32::MIN as i128, i32::MAX as i128), 4);
+    assert_eq!(get_inner_len(i32::MIN as i128 - 1, 0), 8);
+    assert_eq!(get_inner_len(0, i32::MAX as i128 + 1), 8);
+    assert_eq!(get_inner_len(i64::MIN as i128 - 1, 0), 16);
+    assert_eq!(get_inner_len(0, i64::MAX as i128 + 1), 16);
+}
+
+#[quickcheck]
+fn prop_non_negative_assets_are_credits(amount1: u32, amount2: u32) -> TestResult {
+    type MyAsset = Asset<test_asset_non_negative::Value>;
+    let (raw1, raw2) = (amount1 as i128 % 1_000_001, amount2 as i128 % 1_000_001);
+    let credit1 = Credit::<test_asset_non_negative::Value>::try_from(raw1).unwrap();
+    let credit2 = Credit::try_from(raw2).unwrap();
+    let expected = if raw1 >= raw2 {
+        Ok(raw1 - raw2)
+    } else {
+        Err(Error::WouldGoNegative)
+    };
+    let sub = credit1
+        .checked_sub(credit2)
+        .map(|credit| MyAsset::Credit(credit).to_int());
+    let asset_sub = (credit1 - credit2).map(|asset| asset.to_int());
+    TestResult::from_bool(sub == expected && asset_sub == expected)
+}
+
+#[test]
+fn non_negative_assets() {
+    fn only_credits<T: NonNegative>(asset: Asset<T>) -> Credit<T> {
+        asset.into_credit()
+    }
+    type Inventory = Asset<test_asset_non_negative::Value>;
+    type Supply = Asset<test_asset_non_negative_unbounded::Value>;
+
+    let ten = Inventory::try_from(10).unwrap();
+    assert_eq!(Inventory::try_from(-1), Err(Error::WouldGoNegative));
+    assert_eq!(-ten, Err(Error::WouldGoNegative));
+    assert_eq!(ten * -1, Err(Error::WouldGoNegative));
+    assert_eq!(
+        ten.mul_decimal((-5, 1), RoundingMode::HalfEven),
+        Err(Error::WouldGoNegative)
+    );
+    assert_eq!(
+        Credit::<test_asset_non_negative::Value>::try_from("-3"),
+        Err(Error::WouldGoNegative)
+    );
+    let zero = (ten - ten).unwrap();
+    assert!(matches!(zero, Asset::Credit(_)));
+    assert_eq!(Asset::Credit(only_credits(ten)), ten);
+
+    let supply = Supply::try_from("21000000.12345678").unwrap();
+    assert_eq!(only_credits(supply).to_string(), "21000000.12345678");
+    assert_eq!("-0.00000001".parse::<Supply>(), Err(Error::WouldGoNegative));
+}
+
 #[test]
 fn it_works() {
     type MyAsset = Asset<test_asset_low_precision::Value>;
-    let asset1 = MyAsset::try_from((73.5, crate::fixed::FloatRounding::Trunc)).unwrap();
+    let asset1 = MyAsset::try_from((73.5, RoundingMode::TowardZero)).unwrap();
     println!("{:#?}", asset1);
     assert_eq!((73, 50, 100), asset1.to_parts());
 }
+
+#[test]
+fn errors_are_typed() {
+    type MyAsset = Asset<test_asset_with_upper::Value>;
+    let max = 1234 * 10_i128.pow(6);
+    assert_eq!(MyAsset::try_from(1235), Err(Error::AboveBound { max }));
+    assert_eq!(
+        MyAsset::try_from((1, 7)),
+        Err(Error::PrecisionLoss {
+            given: 7,
+            supported: 6
+        })
+    );
+    let asset = MyAsset::try_from(1000).unwrap();
+    assert_eq!(asset + asset, Err(Error::AboveBound { max }));
+    assert_eq!(asset / 0, Err(Error::DivisionByZero));
+    assert_eq!(
+        MyAsset::try_from((f64::NAN, RoundingMode::TowardZero)),
+        Err(Error::NonFinite)
+    );
+    type Overflowing = Asset<test_asset_overflow::Value>;
+    let asset = Overflowing::try_from(2147483647).unwrap();
+    assert_eq!(asset * 2, Err(Error::Overflow));
+}
+
+#[quickcheck]
+fn prop_parse_integers(amount: i128) -> bool {
+    crate::utils::parse::parse_decimal(&amount.to_string()) == Ok((amount, 0))
+}
+
+#[quickcheck]
+fn prop_parse_never_panics(value: String) -> bool {
+    type MyAsset = Asset<test_asset1::Value>;
+    let _ = MyAsset::try_from(value.as_str());
+    true
+}
+
+#[test]
+fn parse_decimal_strings() {
+    use crate::utils::parse::parse_decimal;
+    assert_eq!(parse_decimal("+007.50"), Ok((75, 1)));
+    assert_eq!(parse_decimal(".5"), Ok((5, 1)));
+    assert_eq!(parse_decimal("5."), Ok((5, 0)));
+    assert_eq!(parse_decimal("-1_000_000"), Ok((-1_000_000, 0)));
+    assert_eq!(parse_decimal("1.5e-3"), Ok((15, 4)));
+    assert_eq!(parse_decimal("1.5E+3"), Ok((1500, 0)));
+    assert_eq!(parse_decimal("-0.000"), Ok((0, 0)));
+    assert_eq!(
+        parse_decimal("-170141183460469231731687303715884105728"),
+        Ok((i128::MIN, 0))
+    );
+    // a well formed number that is too big is not a parse error
+    assert_eq!(
+        parse_decimal("170141183460469231731687303715884105728"),
+        Err(Error::Overflow)
+    );
+    assert_eq!(parse_decimal("1e39"), Err(Error::Overflow));
+    assert_eq!(parse_decimal("-1e1000"), Err(Error::Overflow));
+    assert_eq!(parse_decimal("1e99999999999"), Err(Error::Overflow));
+    assert_eq!(parse_decimal("0e99999999999"), Ok((0, 0)));
+    assert_eq!(parse_decimal("1e-99999999999"), Ok((1, u32::MAX)));
+    let error_at = |value: &str| match parse_decimal(value) {
+        Err(Error::Parse { position, .. }) => Some(position),
+        _ => None,
+    };
+    assert_eq!(error_at(""), Some(0));
+    assert_eq!(error_at("abc"), Some(0));
+    assert_eq!(error_at("1.2.3"), Some(3));
+    assert_eq!(error_at("1_"), Some(1));
+    assert_eq!(error_at("_1"), Some(0));
+    assert_eq!(error_at("1._5"), Some(2));
+    assert_eq!(error_at("-"), Some(1));
+    assert_eq!(error_at("1e"), Some(1));
+    assert_eq!(error_at("1e5x"), Some(3));
+
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let asset: MyAsset = "-10.50".parse().unwrap();
+    assert_eq!("1e39".parse::<MyAsset>(), Err(Error::Overflow));
+    assert_eq!("-1e1000".parse::<MyAsset>(), Err(Error::Overflow));
+    assert_eq!((-10, -50, 100), asset.to_parts());
+    assert_eq!(
+        "1.005".parse::<MyAsset>(),
+        Err(Error::PrecisionLoss {
+            given: 3,
+            supported: 2
+        })
+    );
+}
+
+#[quickcheck]
+fn prop_display_parse_round_trip(amount: i128) -> TestResult {
+    type MyAsset = Asset<test_asset1::Value>;
+    let asset = match MyAsset::try_from((amount, 10)) {
+        Ok(asset) => asset,
+        Err(_) => return TestResult::discard(),
+    };
+    let parsed = MyAsset::try_from(asset.to_string().as_str());
+    TestResult::from_bool(parsed == Ok(asset))
+}
+
+#[test]
+fn display_assets() {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let asset = MyAsset::try_from((105, 1)).unwrap();
+    assert_eq!(asset.to_string(), "10.50");
+    let asset = MyAsset::try_from((-5, 2)).unwrap();
+    assert_eq!(asset.to_string(), "-0.05");
+    assert_eq!(format!("{:+}", asset), "-0.05");
+    assert_eq!(format!("{:.1}", asset), "-0.1");
+    assert_eq!(format!("{:.0}", asset), "0");
+    assert_eq!(format!("{:.4}", asset), "-0.0500");
+    let asset = MyAsset::try_from(7).unwrap();
+    assert_eq!(format!("{:+}", asset), "+7.00");
+    assert_eq!(format!("{:>8}", asset), "    7.00");
+    assert_eq!(format!("{:*<8}", asset), "7.00****");
+    assert_eq!(format!("{:08}", asset), "00007.00");
+    type NoDecimals = Asset<test_asset_overflow::Value>;
+    let asset = NoDecimals::try_from(-42).unwrap();
+    assert_eq!(asset.to_string(), "-42");
+    assert_eq!(format!("{:.2}", asset), "-42.00");
+}
+
+#[quickcheck]
+fn prop_convert_keeps_sign(amount: i64, rate: u64, scale: u8) -> TestResult {
+    type From = Asset<test_asset_low_precision::Value>;
+    type To = Asset<test_asset1::Value>;
+    let asset = From::try_from((amount, 2)).unwrap();
+    let rate = Rate::new(rate as u128, scale as u32 % 40);
+    let zero = To::try_from(0).unwrap();
+    match convert(asset, rate, RoundingMode::TowardZero) {
+        Ok(converted @ To::Credit(_)) => TestResult::from_bool(amount >= 0 || converted == zero),
+        Ok(To::Debt(_)) => TestResult::from_bool(amount < 0),
+        Err(_) => TestResult::discard(),
+    }
+}
+
+#[quickcheck]
+fn prop_convert_with_rate_one_is_identity(amount: i64) -> bool {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let asset = MyAsset::try_from((amount, 2)).unwrap();
+    let rate = Rate::new(1, 0);
+    asset.convert(rate, RoundingMode::Floor) == Ok(asset)
+}
+
+#[test]
+fn convert_between_assets() {
+    type Btc = Asset<test_asset1::Value>;
+    type Usd = Asset<test_asset_low_precision::Value>;
+    let rate =
+        Rate::<test_asset1::Value, test_asset_low_precision::Value>::try_from("6543.21").unwrap();
+    let btc = Btc::try_from("-0.00123").unwrap();
+    let usd: Usd = btc.convert(rate, RoundingMode::TowardZero).unwrap();
+    assert_eq!(usd.to_string(), "-8.04");
+    let usd: Usd = btc.convert(rate, RoundingMode::Floor).unwrap();
+    assert_eq!(usd.to_string(), "-8.05");
+    let usd: Usd = btc.convert(rate, RoundingMode::HalfAwayFromZero).unwrap();
+    assert_eq!(usd.to_string(), "-8.05");
+    let btc = Btc::try_from("0.0000000001").unwrap();
+    let usd: Usd = btc.convert(rate, RoundingMode::TowardZero).unwrap();
+    assert_eq!(usd, Usd::try_from(0).unwrap());
+    let usd: Usd = btc.convert(rate, RoundingMode::Ceil).unwrap();
+    assert_eq!(usd.to_string(), "0.01");
+
+    // 10_000_000_000 btc at 10^18 usd each is more than the bound of usd
+    let rate = Rate::new(10_u128.pow(18), 0);
+    let btc = Btc::try_from(10_000_000_000).unwrap();
+    let usd: Result<Usd, Error> = btc.convert(rate, RoundingMode::TowardZero);
+    assert_eq!(
+        usd,
+        Err(Error::AboveBound {
+            max: 18446744073709551615 * 100
+        })
+    );
+    // but the intermediate product does not overflow
+    let rate = Rate::new(10_u128.pow(38), 38);
+    let usd: Usd = btc.convert(rate, RoundingMode::TowardZero).unwrap();
+    assert_eq!(usd.to_string(), "10000000000.00");
+}
+
+fn sum_parts<T>(parts: &[Asset<T>]) -> Asset<T>
+where
+    T: CheckedOps + Copy + std::convert::TryFrom<i128, Error = Error>,
+{
+    parts.iter().fold(Asset::try_from(0).unwrap(), |sum, part| {
+        (sum + *part).unwrap()
+    })
+}
+
+#[quickcheck]
+fn prop_allocate_adds_up(amount: i64, weights: Vec<u64>) -> TestResult {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let asset = MyAsset::try_from((amount, 2)).unwrap();
+    match asset.allocate(&weights) {
+        Ok(parts) => {
+            TestResult::from_bool(parts.len() == weights.len() && sum_parts(&parts) == asset)
+        }
+        Err(Error::DivisionByZero) => {
+            TestResult::from_bool(weights.iter().all(|weight| *weight == 0))
+        }
+        Err(_) => TestResult::from_bool(false),
+    }
+}
+
+#[quickcheck]
+fn prop_split_even_adds_up(amount: i64, n: u8) -> TestResult {
+    type MyAsset = Asset<test_asset_pass_with_i32::Value>;
+    if n == 0 {
+        return TestResult::discard();
+    }
+    let asset = match MyAsset::try_from((amount, 1)) {
+        Ok(asset) => asset,
+        Err(_) => return TestResult::discard(),
+    };
+    let parts = asset.split_even(n as usize).unwrap();
+    let raw: Vec<i128> = parts.iter().map(|part| part.get_inner().to_raw()).collect();
+    let spread = raw.iter().max().unwrap() - raw.iter().min().unwrap();
+    TestResult::from_bool(parts.len() == n as usize && spread <= 1 && sum_parts(&parts) == asset)
+}
+
+#[test]
+fn allocate_assets() {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let parse = |value| MyAsset::try_from(value).unwrap();
+    let asset = parse("10.00");
+    let parts = asset.split_even(3).unwrap();
+    assert_eq!(parts, vec![parse("3.34"), parse("3.33"), parse("3.33")]);
+    let parts = parse("-0.02").split_even(3).unwrap();
+    assert_eq!(parts, vec![parse("-0.01"), parse("-0.01"), parse("0")]);
+    // 0.05 * 1/6 = 0.0083, 0.05 * 2/6 = 0.0166, 0.05 * 3/6 = 0.025
+    let parts = parse("0.05").allocate(&[1, 2, 3]).unwrap();
+    assert_eq!(parts, vec![parse("0.01"), parse("0.02"), parse("0.02")]);
+    let parts = parse("0.01").allocate(&[1, 0, 1]).unwrap();
+    assert_eq!(parts, vec![parse("0.01"), parse("0"), parse("0")]);
+    assert_eq!(asset.allocate(&[]), Err(Error::DivisionByZero));
+    assert_eq!(asset.allocate(&[0, 0]), Err(Error::DivisionByZero));
+    assert_eq!(asset.split_even(0), Err(Error::DivisionByZero));
+}
+
+#[quickcheck]
+fn prop_compound_interest_of_one_period_is_simple(amount: i64, rate: i32) -> TestResult {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let asset = MyAsset::try_from((amount, 2)).unwrap();
+    let rate = (rate as i128, 4);
+    let simple = asset.simple_interest(rate, 1, RoundingMode::HalfAwayFromZero);
+    let each =
+        asset.compound_interest(rate, 1, RoundingMode::HalfAwayFromZero, RoundAt::EachPeriod);
+    let end = asset.compound_interest(rate, 1, RoundingMode::HalfAwayFromZero, RoundAt::End);
+    match simple {
+        Ok(_) => TestResult::from_bool(simple == each && simple == end),
+        Err(_) => TestResult::discard(),
+    }
+}
+
+#[test]
+fn interests() {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let parse = |value| MyAsset::try_from(value).unwrap();
+    let asset = parse("1000");
+    let rounding = RoundingMode::HalfAwayFromZero;
+    assert_eq!(asset.simple_interest((5, 2), 3, rounding), Ok(parse("150")));
+    assert_eq!(
+        parse("-1000").simple_interest((5, 2), 3, rounding),
+        Ok(parse("-150"))
+    );
+    // 1000 * 1.05^3 = 1157.625
+    assert_eq!(
+        asset.compound_interest((5, 2), 3, rounding, RoundAt::End),
+        Ok(parse("157.63"))
+    );
+    assert_eq!(
+        asset.compound_interest((5, 2), 3, RoundingMode::TowardZero, RoundAt::End),
+        Ok(parse("157.62"))
+    );
+    // 1050, 1102.5, 1157.625
+    assert_eq!(
+        asset.compound_interest((5, 2), 3, rounding, RoundAt::EachPeriod),
+        Ok(parse("157.63"))
+    );
+    // 0.01 * 1.4 = 0.014 -> 0.01 every period with EachPeriod
+    let cent = parse("0.01");
+    assert_eq!(
+        cent.compound_interest((4, 1), 5, rounding, RoundAt::EachPeriod),
+        Ok(parse("0"))
+    );
+    // 0.01 * 1.4^5 = 0.0537824
+    assert_eq!(
+        cent.compound_interest((4, 1), 5, rounding, RoundAt::End),
+        Ok(parse("0.04"))
+    );
+    // 1000 * e^0.15 = 1161.834242728283
+    assert_eq!(
+        asset.continuous_interest((5, 2), 3, rounding),
+        Ok(parse("161.83"))
+    );
+    // 1000 * e^-0.15 = 860.707976425057
+    assert_eq!(
+        asset.continuous_interest((-5, 2), 3, rounding),
+        Ok(parse("-139.29"))
+    );
+    assert_eq!(
+        parse("-1000").continuous_interest((5, 2), 3, rounding),
+        Ok(parse("-161.83"))
+    );
+    let max = 18446744073709551615 * 100;
+    assert_eq!(
+        parse("18446744073709551615").simple_interest((1, 0), 1, rounding),
+        Ok(parse("18446744073709551615"))
+    );
+    assert_eq!(
+        parse("18446744073709551615").compound_interest((1, 0), 1, rounding, RoundAt::EachPeriod),
+        Err(Error::AboveBound { max })
+    );
+    assert_eq!(
+        asset.continuous_interest((1000, 0), 1, rounding),
+        Err(Error::Overflow)
+    );
+    // e^-1000 underflows, the interest of a large negative rate tends to -amount
+    assert_eq!(
+        asset.continuous_interest((-1000, 0), 1, rounding),
+        Ok(parse("-1000"))
+    );
+    assert_eq!(
+        parse("-1000").continuous_interest((-1000, 0), 1, rounding),
+        Ok(parse("1000"))
+    );
+    assert_eq!(
+        asset.continuous_interest((i128::MIN + 1, 0), u32::MAX, RoundingMode::Ceil),
+        Ok(parse("-999.99"))
+    );
+    assert_eq!(
+        parse("0").continuous_interest((-1000, 0), 1, RoundingMode::Unnecessary),
+        Ok(parse("0"))
+    );
+}
+
+#[quickcheck]
+fn prop_mul_decimal_integer_factor(amount: i64, operator: i32) -> TestResult {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let asset = MyAsset::try_from((amount, 2)).unwrap();
+    let expected = asset * operator as i128;
+    let decimal = asset * (operator as i128 * 1000, 3, RoundingMode::TowardZero);
+    TestResult::from_bool(expected == decimal)
+}
+
+#[quickcheck]
+fn prop_div_decimal_round_trip(amount: i64, mantissa: i32, scale: u8) -> TestResult {
+    type MyAsset = Asset<test_asset1::Value>;
+    let asset = MyAsset::try_from((amount, 2)).unwrap();
+    let factor = (mantissa as i128, scale as u32 % 9);
+    match asset.mul_decimal(factor, RoundingMode::TowardZero) {
+        // 8 more decimal digits than the amount, so the product is exact
+        Ok(product) if factor.0 != 0 => TestResult::from_bool(
+            product.div_decimal(factor, RoundingMode::TowardZero) == Ok(asset),
+        ),
+        _ => TestResult::discard(),
+    }
+}
+
+/// Check `asset * rhs` and `asset / rhs` against the same operations on i128, for multipliers
+/// that fit in the storage of `T` and for multipliers that do not
+fn scalar_ops_like_i128<T: CheckedOps + Copy>(raw: i128, small: i32, wide: i128) -> TestResult {
+    let asset = match T::from_raw_checked(raw) {
+        Ok(value) => Asset::from_inner(value),
+        Err(_) => return TestResult::discard(),
+    };
+    let in_bound = |raw: &i128| T::MIN <= *raw && *raw <= T::MAX;
+    let to_raw =
+        |asset: Result<Asset<T>, Error>| asset.ok().map(|asset| asset.get_inner().to_raw());
+    let small = small as i128;
+    // a truncated multiplier would be `small`
+    let multipliers = [
+        small,
+        wide,
+        small + (1 << 32),
+        small - (1 << 32),
+        small + (1 << 64),
+        small - (1 << 64),
+        i128::MIN,
+        i128::MAX,
+    ];
+    TestResult::from_bool(multipliers.iter().all(|&rhs| {
+        let div = if rhs == 0 {
+            None
+        } else {
+            raw.checked_div(rhs).filter(in_bound)
+        };
+        to_raw(asset * rhs) == raw.checked_mul(rhs).filter(in_bound) && to_raw(asset / rhs) == div
+    }))
+}
+
+#[quickcheck]
+fn prop_scalar_ops_4_bytes(raw: i32, small: i32, wide: i128) -> TestResult {
+    assert_eq!(test_asset_overflow::Value::STORAGE_BYTES, 4);
+    assert_eq!(test_asset_wallet::Value::STORAGE_BYTES, 4);
+    let raw = raw as i128;
+    let full = scalar_ops_like_i128::<test_asset_overflow::Value>(raw, small, wide);
+    let bounded = scalar_ops_like_i128::<test_asset_wallet::Value>(raw, small, wide);
+    if full.is_failure() || bounded.is_failure() {
+        return TestResult::failed();
+    }
+    full
+}
+
+#[quickcheck]
+fn prop_scalar_ops_8_bytes(raw: i64, small: i32, wide: i128) -> TestResult {
+    assert_eq!(test_asset_i64_storage::Value::STORAGE_BYTES, 8);
+    assert_eq!(test_asset_i64_bounded::Value::STORAGE_BYTES, 8);
+    let raw = raw as i128;
+    let full = scalar_ops_like_i128::<test_asset_i64_storage::Value>(raw, small, wide);
+    let bounded = scalar_ops_like_i128::<test_asset_i64_bounded::Value>(raw, small, wide);
+    if full.is_failure() || bounded.is_failure() {
+        return TestResult::failed();
+    }
+    full
+}
+
+#[quickcheck]
+fn prop_scalar_ops_16_bytes(raw: i128, small: i32, wide: i128) -> TestResult {
+    assert_eq!(test_asset_unbounded::Value::STORAGE_BYTES, 16);
+    assert_eq!(test_asset1::Value::STORAGE_BYTES, 16);
+    let full = scalar_ops_like_i128::<test_asset_unbounded::Value>(raw, small, wide);
+    let bounded = scalar_ops_like_i128::<test_asset1::Value>(raw, small, wide);
+    if full.is_failure() || bounded.is_failure() {
+        return TestResult::failed();
+    }
+    full
+}
+
+#[test]
+fn multipliers_wider_than_the_storage() {
+    type Small = Asset<test_asset_overflow::Value>;
+    type Medium = Asset<test_asset_i64_storage::Value>;
+
+    let one = Small::try_from(1).unwrap();
+    assert_eq!(one * 4_294_967_297, Err(Error::Overflow));
+    assert_eq!(one * -4_294_967_296, Err(Error::Overflow));
+    assert_eq!((one * 0).unwrap(), Small::try_from(0).unwrap());
+    assert_eq!(one / 4_294_967_297, Small::try_from(0));
+    let max = Small::try_from(i32::MAX as i128).unwrap();
+    assert_eq!(max / (1 << 32), Small::try_from(0));
+    assert_eq!(
+        Small::try_from(0).unwrap() * 18_446_744_073_709_551_617,
+        Small::try_from(0)
+    );
+
+    let one = Medium::try_from(1).unwrap();
+    assert_eq!(one * 18_446_744_073_709_551_617, Err(Error::Overflow));
+    let max = Medium::try_from(i64::MAX as i128).unwrap();
+    assert_eq!(max / (i64::MAX as i128 + 1), Medium::try_from(0));
+    // truncated to an i64 the divisor would be -i64::MAX and the quotient 1
+    assert_eq!(
+        (-max).unwrap() / (i64::MAX as i128 + 2),
+        Medium::try_from(0)
+    );
+}
+
+#[quickcheck]
+fn prop_mul_div_rounds_once(amount: i32, num: i32, den: i32) -> TestResult {
+    type MyAsset = Asset<test_asset_unbounded::Value>;
+    if den == 0 {
+        return TestResult::discard();
+    }
+    let asset = MyAsset::try_from((amount, 2)).unwrap();
+    // the product of two i32 fits in an i128, so multiplying first is exact
+    let expected = (asset * num as i128)
+        .and_then(|product| product.div_rounded(den as i128, RoundingMode::HalfEven));
+    TestResult::from_bool(
+        asset.mul_div(num as i128, den as i128, RoundingMode::HalfEven) == expected,
+    )
+}
+
+#[test]
+fn mul_div() {
+    use RoundingMode::*;
+    type Wallet = Asset<test_asset_wallet::Value>;
+    type Unbounded = Asset<test_asset_unbounded::Value>;
+
+    // the product exceeds the bound and the i32 storage but the result does not
+    let amount = Wallet::try_from(999_999).unwrap();
+    assert!((amount * 1_000_000).is_err());
+    let share = amount.mul_div(1_000_000, 3_000_000, HalfEven).unwrap();
+    assert_eq!(share.to_string(), "333333.00");
+    let share = amount.mul_div(2, 3, HalfEven).unwrap();
+    assert_eq!(share.to_string(), "666666.00");
+    let share = Wallet::try_from("10.00").unwrap().mul_div(1, 3, Ceil);
+    assert_eq!(share.unwrap().to_string(), "3.34");
+    assert_eq!(
+        Wallet::try_from("-10.00").unwrap().mul_div(-2, -3, Floor),
+        Wallet::try_from("-6.67")
+    );
+    assert_eq!(
+        amount.mul_div(3, 2, HalfEven),
+        Err(Error::AboveBound { max: 100_000_000 })
+    );
+    assert_eq!(amount.mul_div(1, 0, HalfEven), Err(Error::DivisionByZero));
+    assert_eq!(
+        Wallet::try_from("0.01").unwrap().mul_div(1, 2, Unnecessary),
+        Err(Error::Inexact)
+    );
+
+    // 256 bit intermediate for values stored in 16 bytes
+    let big = Unbounded::try_from((i128::MAX / 3, 2)).unwrap();
+    assert_eq!(big.mul_div(i128::MAX, i128::MAX, HalfEven), Ok(big));
+    let raw = big.mul_div(3, 4, TowardZero).unwrap().get_inner().to_raw();
+    assert_eq!(raw, i128::MAX / 4);
+    assert_eq!(big.mul_div(4, 1, HalfEven), Err(Error::Overflow));
+
+    // the result is rounded to a multiple of the tick
+    let tick = Asset::<test_asset_tick::Value>::try_from("10.00").unwrap();
+    assert_eq!(tick.mul_div(1, 3, HalfEven).unwrap().to_string(), "3.35");
+}
+
+#[test]
+fn mul_and_div_for_decimal_factors() {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let parse = |value| MyAsset::try_from(value).unwrap();
+    let rounding = RoundingMode::HalfAwayFromZero;
+    assert_eq!(parse("10.00") * (1075, 3, rounding), Ok(parse("10.75")));
+    // 10.01 * 1.075 = 10.76075
+    assert_eq!(parse("10.01") * (1075, 3, rounding), Ok(parse("10.76")));
+    assert_eq!(
+        parse("10.01") * (1075, 3, RoundingMode::Ceil),
+        Ok(parse("10.77"))
+    );
+    assert_eq!(
+        parse("-10.01") * (3, 1, RoundingMode::Floor),
+        Ok(parse("-3.01"))
+    );
+    assert_eq!(parse("10") / (3, 1, rounding), Ok(parse("33.33")));
+    assert_eq!(
+        parse("10") / (-3, 1, RoundingMode::Floor),
+        Ok(parse("-33.34"))
+    );
+    assert_eq!(parse("10") / (0, 1, rounding), Err(Error::DivisionByZero));
+    let trunc = RoundingMode::TowardZero;
+    // 1.005 as f64 is 1.00499999999999989341858963598497211933135986328125
+    assert_eq!(parse("100") * (1.005, trunc), Ok(parse("100.5")));
+    assert_eq!(
+        parse("1") * (1.005, RoundingMode::HalfAwayFromZero),
+        Ok(parse("1.01"))
+    );
+    assert_eq!(parse("1") / (0.3, trunc), Ok(parse("3.33")));
+    assert_eq!(parse("1") * (f64::INFINITY, trunc), Err(Error::NonFinite));
+    // a finite factor too big for a decimal tuple is not a parse error
+    let max = 18446744073709551615 * 100;
+    assert_eq!(
+        parse("1") * (1e40, rounding),
+        Err(Error::AboveBound { max })
+    );
+    assert_eq!(
+        parse("1") * (-1e300, rounding),
+        Err(Error::BelowBound { min: -max })
+    );
+    assert_eq!(parse("0") * (f64::MAX, rounding), Ok(parse("0")));
+    assert_eq!(parse("1") / (1e40, rounding), Ok(parse("0")));
+    assert_eq!(parse("1") / (1e40, RoundingMode::Ceil), Ok(parse("0.01")));
+    assert_eq!(
+        parse("1") / (-1e300, RoundingMode::Floor),
+        Ok(parse("-0.01"))
+    );
+    assert_eq!(
+        parse("1") / (1e40, RoundingMode::Unnecessary),
+        Err(Error::Inexact)
+    );
+    let big = Asset::<i128>::try_from(i128::MAX).unwrap();
+    assert_eq!(big / (3e38, rounding), Ok(Asset::try_from(1).unwrap()));
+    assert_eq!(big / (4e38, rounding), Ok(Asset::try_from(0).unwrap()));
+    assert_eq!(big * (1e40, rounding), Err(Error::Overflow));
+    // the intermediate product does not overflow
+    let max = parse("18446744073709551615");
+    assert_eq!(max * (10_i128.pow(37), 37, rounding), Ok(max));
+    assert_eq!(max / (10_i128.pow(37), 37, rounding), Ok(max));
+    assert_eq!(
+        max * (11, 1, rounding),
+        Err(Error::AboveBound {
+            max: 18446744073709551615 * 100
+        })
+    );
+}
+
+#[test]
+fn rounding_modes() {
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    let parse = |value: &str| MyAsset::try_from(value).unwrap();
+    let cases = [
+        // value, HalfEven, HalfUp, HalfDown, HalfTowardZero, HalfAwayFromZero, AwayFromZero,
+        // TowardZero, Floor, Ceil
+        [
+            "1.125", "1.12", "1.13", "1.12", "1.12", "1.13", "1.13", "1.12", "1.12", "1.13",
+        ],
+        [
+            "1.135", "1.14", "1.14", "1.13", "1.13", "1.14", "1.14", "1.13", "1.13", "1.14",
+        ],
+        [
+            "-1.125", "-1.12", "-1.12", "-1.13", "-1.12", "-1.13", "-1.13", "-1.12", "-1.13",
+            "-1.12",
+        ],
+        [
+            "1.121", "1.12", "1.12", "1.12", "1.12", "1.12", "1.13", "1.12", "1.12", "1.13",
+        ],
+        [
+            "-1.129", "-1.13", "-1.13", "-1.13", "-1.13", "-1.13", "-1.13", "-1.12", "-1.13",
+            "-1.12",
+        ],
+        [
+            "1.12", "1.12", "1.12", "1.12", "1.12", "1.12", "1.12", "1.12", "1.12", "1.12",
+        ],
+    ];
+    let modes = [
+        RoundingMode::HalfEven,
+        RoundingMode::HalfUp,
+        RoundingMode::HalfDown,
+        RoundingMode::HalfTowardZero,
+        RoundingMode::HalfAwayFromZero,
+        RoundingMode::AwayFromZero,
+        RoundingMode::TowardZero,
+        RoundingMode::Floor,
+        RoundingMode::Ceil,
+    ];
+    for case in cases.iter() {
+        for (mode, expected) in modes.iter().zip(case[1..].iter()) {
+            let rounded = MyAsset::parse_rounded(case[0], *mode);
+            assert_eq!(rounded, Ok(parse(expected)), "{} {:?}", case[0], mode);
+        }
+    }
+    let unnecessary = RoundingMode::Unnecessary;
+    assert_eq!(
+        MyAsset::parse_rounded("1.12", unnecessary),
+        Ok(parse("1.12"))
+    );
+    assert_eq!(
+        MyAsset::parse_rounded("1.121", unnecessary),
+        Err(Error::Inexact)
+    );
+    assert_eq!(parse("1") / (3, unnecessary), Err(Error::Inexact));
+    assert_eq!(parse("1") / (4, unnecessary), Ok(parse("0.25")));
+
+    assert_eq!(
+        parse("0.05") / (2, RoundingMode::HalfEven),
+        Ok(parse("0.02"))
+    );
+    assert_eq!(
+        parse("0.07") / (2, RoundingMode::HalfEven),
+        Ok(parse("0.04"))
+    );
+    assert_eq!(parse("0.07") / 2, Ok(parse("0.03")));
+    assert_eq!(
+        parse("2.5").round_to(0, RoundingMode::HalfEven),
+        Ok(parse("2"))
+    );
+    assert_eq!(
+        parse("3.5").round_to(0, RoundingMode::HalfEven),
+        Ok(parse("4"))
+    );
+    assert_eq!(
+        parse("3.55").round_to(1, RoundingMode::HalfDown),
+        Ok(parse("3.5"))
+    );
+    assert_eq!(
+        parse("3.55").round_to(2, RoundingMode::Unnecessary),
+        Ok(parse("3.55"))
+    );
+    assert_eq!(
+        MyAsset::try_from((0.125, RoundingMode::HalfEven)),
+        Ok(parse("0.12"))
+    );
+    assert_eq!(
+        MyAsset::try_from((-0.125, RoundingMode::HalfUp)),
+        Ok(parse("-0.12"))
+    );
+    assert_eq!(
+        MyAsset::try_from((0.5, RoundingMode::Unnecessary)),
+        Ok(parse("0.5"))
+    );
+}
+
+const ALL_ROUNDING_MODES: [RoundingMode; 10] = [
+    RoundingMode::HalfEven,
+    RoundingMode::HalfUp,
+    RoundingMode::HalfDown,
+    RoundingMode::HalfTowardZero,
+    RoundingMode::HalfAwayFromZero,
+    RoundingMode::AwayFromZero,
+    RoundingMode::TowardZero,
+    RoundingMode::Floor,
+    RoundingMode::Ceil,
+    RoundingMode::Unnecessary,
+];
+
+// mantissa * 2^-exp is exactly representable as a f64 and rounded like an integer division
+#[quickcheck]
+fn prop_exact_float_conversion(mantissa: i64, exp: u8, mode: u8) -> bool {
+    use super::FloatConversion;
+    use crate::utils::wide::mul_div_rounded;
+    type MyAsset = Asset<test_asset_unbounded::Value>;
+    let mantissa = (mantissa % (1 << 53)) as i128;
+    let exp = (exp % 120) as i32;
+    let mode = ALL_ROUNDING_MODES[mode as usize % ALL_ROUNDING_MODES.len()];
+    let value = mantissa as f64 * 2_f64.powi(-exp);
+    let expected = mul_div_rounded(mantissa, 100, 1 << exp, mode, 1);
+    let raw = MyAsset::from_f64(value, mode, FloatConversion::Exact)
+        .map(|asset| asset.get_inner().to_raw());
+    // mantissa * 2^shift * 100 fits in an i128
+    let shift = exp % 60;
+    let scaled = mantissa as f64 * 2_f64.powi(shift);
+    let product = (mantissa << shift) * 100;
+    let scaled_raw = MyAsset::from_f64(scaled, mode, FloatConversion::Exact)
+        .map(|asset| asset.get_inner().to_raw());
+    raw == expected && scaled_raw == Ok(product)
+}
+
+// the shortest representation of a float is the one printed by `{}` and parsed back
+#[quickcheck]
+fn prop_shortest_float_conversion(value: f64, mode: u8) -> TestResult {
+    type MyAsset = Asset<test_asset1::Value>;
+    if !value.is_finite() {
+        return TestResult::discard();
+    }
+    let mode = ALL_ROUNDING_MODES[mode as usize % ALL_ROUNDING_MODES.len()];
+    let from_float = MyAsset::try_from((value, mode));
+    match MyAsset::parse_rounded(&value.to_string(), mode) {
+        Ok(parsed) => TestResult::from_bool(from_float == Ok(parsed)),
+        Err(Error::Parse { .. }) => TestResult::discard(),
+        Err(error) => TestResult::from_bool(from_float == Err(error)),
+    }
+}
+
+#[test]
+fn float_conversions() {
+    use super::FloatConversion::{self, Exact, Shortest};
+    use RoundingMode::*;
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+    type Unbounded = Asset<test_asset_unbounded::Value>;
+    type Wallet = Asset<test_asset_wallet::Value>;
+    type Tick = Asset<test_asset_tick::Value>;
+    let parse = |value: &str| MyAsset::try_from(value).unwrap();
+
+    assert_eq!(FloatConversion::default(), Shortest);
+    // 1.005 as f64 is 1.00499999999999989341858963598497211933135986328125
+    assert_eq!(MyAsset::try_from((1.005, HalfUp)), Ok(parse("1.01")));
+    assert_eq!(MyAsset::from_f64(1.005, HalfUp, Exact), Ok(parse("1.00")));
+    assert_eq!(MyAsset::from_f64(1.005, Ceil, Exact), Ok(parse("1.01")));
+    assert_eq!(
+        MyAsset::from_f64(1.005, Unnecessary, Exact),
+        Err(Error::Inexact)
+    );
+    assert_eq!(
+        MyAsset::from_f64(-0.375, HalfEven, Exact),
+        Ok(parse("-0.38"))
+    );
+    assert_eq!(MyAsset::from_f64(-0.0, Floor, Exact), Ok(parse("0")));
+
+    // large values keep every digit
+    let big = 2_f64.powi(100);
+    assert_eq!(
+        Unbounded::from_f64(big, HalfEven, Exact)
+            .unwrap()
+            .to_string(),
+        "1267650600228229401496703205376.00"
+    );
+    assert_eq!(
+        Unbounded::try_from((big, HalfEven)).unwrap().to_string(),
+        "1267650600228229400000000000000.00"
+    );
+    assert_eq!(
+        Unbounded::try_from((1e20, TowardZero)).unwrap().to_string(),
+        "100000000000000000000.00"
+    );
+    assert_eq!(Unbounded::try_from((1e300, HalfEven)), Err(Error::Overflow));
+    assert_eq!(
+        Unbounded::from_f64(-1e300, HalfEven, Exact),
+        Err(Error::Overflow)
+    );
+
+    // subnormals
+    let tiny = f64::from_bits(1);
+    assert_eq!(
+        Unbounded::from_f64(tiny, Ceil, Exact),
+        Unbounded::try_from("0.01")
+    );
+    assert_eq!(
+        Unbounded::from_f64(-tiny, Floor, Shortest),
+        Unbounded::try_from("-0.01")
+    );
+    assert_eq!(
+        Unbounded::from_f64(tiny, HalfEven, Exact),
+        Unbounded::try_from("0")
+    );
+
+    // the bound is checked on the rounded value
+    let max = 100_000_000;
+    assert!(Wallet::try_from((1_000_000.004, HalfEven)).is_ok());
+    assert_eq!(
+        Wallet::try_from((1_000_000.005, HalfUp)),
+        Err(Error::AboveBound { max })
+    );
+    assert_eq!(
+        Wallet::try_from((1e300, HalfUp)),
+        Err(Error::AboveBound { max })
+    );
+    assert_eq!(
+        Wallet::from_f64(-1e30, HalfUp, Exact),
+        Err(Error::BelowBound { min: -50_000 })
+    );
+
+    // rounded once to a multiple of the tick
+    assert_eq!(Tick::try_from((1.075, HalfEven)), Tick::try_from("1.10"));
+    assert_eq!(
+        Tick::from_f64(1.075, HalfEven, Exact),
+        Tick::try_from("1.05")
+    );
+
+    assert_eq!(
+        Asset::<i64>::from_f64(2.5, HalfEven, Exact).map(Asset::get_inner),
+        Ok(2)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_representations() {
+    use crate::Credit;
+    use serde::{Deserialize, Serialize};
+    type MyAsset = Asset<test_asset_low_precision::Value>;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Account {
+        balance: MyAsset,
+        #[serde(with = "crate::serde::units")]
+        units: MyAsset,
+        #[serde(with = "crate::serde::object")]
+        object: MyAsset,
+        limit: Credit<test_asset_low_precision::Value>,
+    }
+
+    let amount: MyAsset = "-10.5".parse().unwrap();
+    let limit = match "100".parse::<MyAsset>().unwrap() {
+        Asset::Credit(limit) => limit,
+        _ => panic!("wrong kind"),
+    };
+    let account = Account {
+        balance: amount,
+        units: amount,
+        object: amount,
+        limit,
+    };
+    let json = serde_json::to_string(&account).unwrap();
+    assert_eq!(
+        json,
+        r#"{"balance":"-10.50","units":-1050,"object":{"amount":"-10.50","asset":"test_asset_low_precision"},"limit":"100.00"}"#
+    );
+    assert_eq!(serde_json::from_str::<Account>(&json).unwrap(), account);
+
+    let value: test_asset_low_precision::Value = serde_json::from_str(r#""1.25""#).unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#""1.25""#);
+}
+
+#[cfg(feature = "serde")]
+#[quickcheck]
+fn prop_serde_round_trips_credit_and_debt(amount: i64) -> bool {
+    use crate::serde::Amount;
+    use crate::{Credit, Debt};
+    use serde::{Deserialize, Serialize};
+    type Value = test_asset_low_precision::Value;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Account<A: Amount> {
+        decimal: A,
+        #[serde(with = "crate::serde::units")]
+        units: A,
+        #[serde(with = "crate::serde::object")]
+        object: A,
+    }
+
+    fn round_trips<A>(amount: A) -> bool
+    where
+        A: Amount + Serialize + for<'de> Deserialize<'de> + Copy + PartialEq + std::fmt::Debug,
+    {
+        let account = Account {
+            decimal: amount,
+            units: amount,
+            object: amount,
+        };
+        let json = serde_json::to_string(&account).unwrap();
+        serde_json::from_str::<Account<A>>(&json).unwrap() == account
+    }
+
+    match Asset::<Value>::try_from((amount as i128, 2)).unwrap() {
+        Asset::Credit(credit) => round_trips::<Credit<Value>>(credit),
+        Asset::Debt(debt) => round_trips::<Debt<Value>>(debt),
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_rejects_invalid_amounts() {
+    use crate::{Credit, Debt};
+    type Value = test_asset_with_upper::Value;
+
+    let error = |json: &str| {
+        serde_json::from_str::<Asset<Value>>(json)
+            .unwrap_err()
+            .to_string()
+    };
+    assert!(error(r#""1.0000001""#).contains("7 decimal digits"));
+    assert!(error(r#""1235""#).contains("bound"));
+    assert!(error("1.5").contains("decimal string"));
+    assert!(error(r#""1.2.3""#).contains("invalid decimal"));
+    assert!(serde_json::from_str::<Credit<Value>>(r#""-1""#).is_err());
+    // a debt is never 0, so "0" is only a valid credit
+    assert!(serde_json::from_str::<Debt<Value>>(r#""0""#).is_err());
+    assert!(serde_json::from_str::<Credit<Value>>(r#""0""#).is_ok());
+    assert!(serde_json::from_str::<Debt<Value>>(r#""-1""#).is_ok());
+
+    #[derive(serde::Deserialize, Debug)]
+    struct Units(#[serde(with = "crate::serde::units")] Asset<Value>);
+    assert!(serde_json::from_str::<Units>("1234000000").is_ok());
+    assert!(serde_json::from_str::<Units>("1234000001").is_err());
+
+    #[derive(serde::Deserialize, Debug)]
+    struct Object(#[serde(with = "crate::serde::object")] Asset<Value>);
+    let object = serde_json::from_str::<Object>(r#"{"asset":"usd","amount":"1"}"#);
+    assert!(object.unwrap_err().to_string().contains("usd"));
+    let object = serde_json::from_str::<Object>(r#"{"asset":"test_asset_with_upper"}"#);
+    assert!(object.unwrap_err().to_string().contains("amount"));
+}
+
+#[test]
+fn asset_info() {
+    use crate::fixed::{UNBOUNDED, UNBOUNDED_MIN};
+
+    fn info<T: CheckedOps>() -> (&'static str, u32, (i128, u32), i128, i128, usize) {
+        (
+            T::NAME,
+            T::DECIMALS,
+            T::UNIT,
+            T::MIN,
+            T::MAX,
+            T::STORAGE_BYTES,
+        )
+    }
+    assert_eq!(
+        info::<test_asset_tick::Value>(),
+        ("test_asset_tick", 2, (5, 2), -100_000_000, 100_000_000, 4)
+    );
+    assert_eq!(
+        info::<test_asset_wallet::Value>(),
+        ("test_asset_wallet", 2, (1, 2), -50_000, 100_000_000, 4)
+    );
+    assert_eq!(
+        info::<test_asset_non_negative::Value>(),
+        ("test_asset_non_negative", 0, (1, 0), 0, 1_000_000, 4)
+    );
+    assert_eq!(
+        info::<test_asset_unbounded::Value>(),
+        (
+            "test_asset_unbounded",
+            2,
+            (1, 2),
+            UNBOUNDED_MIN,
+            UNBOUNDED,
+            16
+        )
+    );
+    assert_eq!(info::<test_asset1::Value>().5, 16);
+    assert_eq!(test_asset1::Value::CODE, None);
+    assert_eq!(test_asset1::Value::SYMBOL, None);
+    assert_eq!(test_asset1::Value::ROUNDING, RoundingMode::HalfEven);
+    assert_eq!(<u8 as AssetInfo>::ROUNDING, RoundingMode::HalfEven);
+}
+
+#[quickcheck]
+fn prop_dyn_asset_like_static(amount1: i32, amount2: i32, n: i16) -> TestResult {
+    use super::{AssetSpec, DynAsset};
+    use std::sync::Arc;
+    type MyAsset = Asset<test_asset_wallet::Value>;
+
+    let spec = Arc::new(AssetSpec::of::<test_asset_wallet::Value>());
+    let (asset1, asset2) = match (
+        MyAsset::try_from((amount1 as i128, 2)),
+        MyAsset::try_from((amount2 as i128, 2)),
+    ) {
+        (Ok(asset1), Ok(asset2)) => (asset1, asset2),
+        _ => return TestResult::discard(),
+    };
+    let dyn1 = DynAsset::from_raw(&spec, amount1 as i128).unwrap();
+    let dyn2 = DynAsset::from_asset(&spec, asset2).unwrap();
+    let raw = |asset: Result<MyAsset, Error>| asset.map(|asset| asset.get_inner().to_raw());
+    let dyn_raw = |asset: Result<DynAsset, Error>| asset.map(|asset| asset.to_raw());
+    let n = n as i128;
+
+    // the static asset is stored in an i32, so a product can overflow the storage before the
+    // bound is checked
+    let mul_like_static = match (raw(asset1 * n), dyn_raw(dyn1.clone() * n)) {
+        (Ok(product), dyn_product) => dyn_product == Ok(product),
+        (Err(_), dyn_product) => dyn_product.is_err(),
+    };
+    TestResult::from_bool(
+        raw(asset1 + asset2) == dyn_raw(dyn1.clone() + dyn2.clone())
+            && raw(asset1 - asset2) == dyn_raw(dyn1.clone() - dyn2.clone())
+            && raw(asset1 / n) == dyn_raw(dyn1.clone() / n)
+            && raw(asset1.div_rounded(n, RoundingMode::HalfEven))
+                == dyn_raw(dyn1.div_rounded(n, RoundingMode::HalfEven))
+            && mul_like_static
+            && asset1.to_string() == dyn1.to_string()
+            && asset1.partial_cmp(&asset2) == dyn1.partial_cmp(&dyn2),
+    )
+}
+
+#[test]
+fn dyn_asset() {
+    use super::{AssetSpec, DynAsset};
+    use std::sync::Arc;
+
+    let token = Arc::new(AssetSpec::new("token", 18, 0, 21_000_000 * 10_i128.pow(18)).unwrap());
+    let other = Arc::new(AssetSpec::unbounded("other", 18).unwrap());
+    let amount = DynAsset::parse(&token, "1.5").unwrap();
+    assert_eq!(amount.to_string(), "1.500000000000000000");
+    assert_eq!(amount.to_raw(), 15 * 10_i128.pow(17));
+    assert_eq!(
+        DynAsset::parse(&token, "1.0000000000000000001"),
+        Err(Error::PrecisionLoss {
+            given: 19,
+            supported: 18
+        })
+    );
+    let rounded = DynAsset::parse_rounded(&token, "1.0000000000000000005", RoundingMode::HalfUp);
+    assert_eq!(rounded.unwrap().to_raw(), 10_i128.pow(18) + 1);
+    assert_eq!(-amount.clone(), Err(Error::WouldGoNegative));
+    assert_eq!(
+        DynAsset::parse(&token, "21000000.000000000000000001"),
+        Err(Error::AboveBound {
+            max: 21_000_000 * 10_i128.pow(18)
+        })
+    );
+
+    // different specs do not mix
+    let other_amount = DynAsset::parse(&other, "1.5").unwrap();
+    assert_eq!(
+        amount.clone() + other_amount.clone(),
+        Err(Error::DifferentAssets)
+    );
+    assert_eq!(amount.partial_cmp(&other_amount), None);
+    assert!(amount != other_amount);
+
+    // a spec built at runtime equal to the spec of a static asset bridges to it
+    let chf = Arc::new(
+        AssetSpec::new("test_asset_tick", 2, -100_000_000, 100_000_000)
+            .unwrap()
+            .with_tick(5)
+            .unwrap(),
+    );
+    assert_eq!(*chf, AssetSpec::of::<test_asset_tick::Value>());
+    let dyn_chf = DynAsset::parse(&chf, "10.05").unwrap();
+    assert_eq!(
+        DynAsset::parse(&chf, "10.01"),
+        Err(Error::NotMultipleOfTick { tick: 5 })
+    );
+    assert_eq!((dyn_chf.clone() / 2).unwrap().to_string(), "5.00");
+    let chf_asset = Asset::<test_asset_tick::Value>::try_from(dyn_chf.clone()).unwrap();
+    assert_eq!(chf_asset.to_string(), "10.05");
+    assert_eq!(DynAsset::from(chf_asset), dyn_chf);
+    assert_eq!(
+        Asset::<test_asset_wallet::Value>::try_from(dyn_chf),
+        Err(Error::DifferentAssets)
+    );
+    assert_eq!(
+        DynAsset::from_asset(&token, chf_asset),
+        Err(Error::DifferentAssets)
+    );
+
+    assert_eq!(
+        AssetSpec::new("bad", 39, 0, 1),
+        Err(Error::PrecisionLoss {
+            given: 39,
+            supported: 38
+        })
+    );
+    assert!(AssetSpec::new("bad", 2, 1, 10).is_err());
+    assert!(AssetSpec::unbounded("bad", 2)
+        .unwrap()
+        .with_tick(0)
+        .is_err());
+}
+
+#[quickcheck]
+fn prop_primitive_like_integer(amount1: i32, amount2: i32, n: i32) -> bool {
+    let asset1 = Asset::<i32>::try_from(amount1 as i128).unwrap();
+    let asset2 = Asset::<i32>::try_from(amount2 as i128).unwrap();
+    let inner = |asset: Result<Asset<i32>, Error>| asset.ok().map(Asset::get_inner);
+    let div = if n == 0 { None } else { amount1.checked_div(n) };
+    inner(asset1 + asset2) == amount1.checked_add(amount2)
+        && inner(asset1 - asset2) == amount1.checked_sub(amount2)
+        && inner(asset1 * n as i128) == amount1.checked_mul(n)
+        && inner(asset1 / n as i128) == div
+        && inner(-asset1) == amount1.checked_neg()
+        && asset1.to_string() == amount1.to_string()
+        && matches!(asset1, Asset::Credit(_)) == (amount1 >= 0)
+}
+
+#[test]
+fn primitive_assets() {
+    type Count = Asset<u64>;
+    let count = Count::try_from(10).unwrap();
+    assert_eq!(count.to_string(), "10");
+    assert_eq!(Count::try_from("25").unwrap().get_inner(), 25);
+    assert_eq!(Count::try_from("-1"), Err(Error::WouldGoNegative));
+    assert_eq!(
+        Count::try_from("2.5"),
+        Err(Error::PrecisionLoss {
+            given: 1,
+            supported: 0
+        })
+    );
+    assert_eq!(
+        (count - Count::try_from(11).unwrap()),
+        Err(Error::WouldGoNegative)
+    );
+    assert_eq!((count * 3).unwrap().get_inner(), 30);
+    assert_eq!(count / 0, Err(Error::DivisionByZero));
+    assert_eq!(Count::try_from(u64::MAX as i128 + 1), Err(Error::Overflow));
+    assert!(Credit::<u64>::try_from(7).is_ok());
+
+    type Points = Asset<i32>;
+    let points: Points = "-1_000".parse().unwrap();
+    assert!(matches!(points, Asset::Debt(_)));
+    assert_eq!(format!("{:+}", (points / 3).unwrap()), "-333");
+    assert_eq!(
+        Points::try_from((2.5, RoundingMode::HalfEven))
+            .unwrap()
+            .get_inner(),
+        2
+    );
+    assert_eq!(Points::try_from(i32::MAX as i128 + 1), Err(Error::Overflow));
+    assert_eq!((points * i128::MAX), Err(Error::Overflow));
+
+    let big = Asset::<i128>::try_from(i128::MAX).unwrap();
+    assert_eq!(big + Asset::try_from(1).unwrap(), Err(Error::Overflow));
+    assert_eq!(big.get_inner().to_raw(), i128::MAX);
+
+    assert_eq!(<u8 as AssetInfo>::NAME, "u8");
+    assert_eq!(<i64 as AssetInfo>::STORAGE_BYTES, 8);
+    assert_eq!(<u16 as AssetInfo>::MAX, 65_535);
+    assert_eq!(<i16 as AssetInfo>::MIN, -32_768);
+}
+
+#[quickcheck]
+fn prop_saturating_clamps_to_bound(amount1: i32, amount2: i32, n: i16) -> TestResult {
+    type MyAsset = Asset<test_asset_wallet::Value>;
+    let (min, max) = (
+        <test_asset_wallet::Value as AssetInfo>::MIN,
+        <test_asset_wallet::Value as AssetInfo>::MAX,
+    );
+    let (asset1, asset2) = match (
+        MyAsset::try_from((amount1 as i128, 2)),
+        MyAsset::try_from((amount2 as i128, 2)),
+    ) {
+        (Ok(asset1), Ok(asset2)) => (asset1, asset2),
+        _ => return TestResult::discard(),
+    };
+    let (raw1, raw2, n) = (amount1 as i128, amount2 as i128, n as i128);
+    let clamp = |raw: i128| (raw.max(min).min(max), raw < min || raw > max);
+    let raw = |(asset, clamped): (MyAsset, bool)| (asset.get_inner().to_raw(), clamped);
+
+    let div_like_clamp = n == 0 || raw(asset1.overflowing_div(n)) == clamp(raw1 / n);
+    TestResult::from_bool(
+        raw(asset1.overflowing_add(asset2)) == clamp(raw1 + raw2)
+            && raw(asset1.overflowing_sub(asset2)) == clamp(raw1 - raw2)
+            && raw(asset1.overflowing_neg()) == clamp(-raw1)
+            && raw(asset1.overflowing_mul(n)) == clamp(raw1 * n)
+            && div_like_clamp
+            && asset1.saturating_add(asset2).get_inner().to_raw() == clamp(raw1 + raw2).0,
+    )
+}
+
+#[test]
+fn saturating_arithmetic() {
+    type Unbounded = Asset<test_asset_unbounded::Value>;
+    let big = Unbounded::try_from((i128::MAX, 2)).unwrap();
+    assert_eq!(big.saturating_mul(2), big);
+    assert_eq!(big.overflowing_mul(-2).0.get_inner().to_raw(), i128::MIN);
+    let smallest = big.saturating_mul(-2);
+    assert_eq!(smallest.overflowing_div(-1), (big, true));
+    assert_eq!(smallest.saturating_neg(), big);
+
+    // the clamped value is a multiple of the tick
+    type Tick = Asset<test_asset_tick::Value>;
+    let high = Tick::try_from("999999.95").unwrap();
+    assert_eq!(high.saturating_mul(3).to_string(), "1000000.00");
+    assert_eq!(
+        high.saturating_neg().saturating_mul(3).to_string(),
+        "-1000000.00"
+    );
+
+    // a credit stays a credit and a debt a debt
+    let credit = Asset::<test_asset_wallet::Value>::try_from(999_999)
+        .unwrap()
+        .abs()
+        .unwrap();
+    let (sum, clamped) = credit.overflowing_add(credit);
+    assert!(clamped);
+    assert_eq!(sum.0.to_raw(), <test_asset_wallet::Value as AssetInfo>::MAX);
+    let debt = match Asset::<test_asset_wallet::Value>::try_from(-400).unwrap() {
+        Asset::Debt(debt) => debt,
+        Asset::Credit(_) => unreachable!(),
+    };
+    assert_eq!(debt.saturating_sub(credit).0.to_raw(), -50_000);
+    assert_eq!(debt.saturating_add(debt).0.to_raw(), -50_000);
+    assert_eq!(
+        credit.saturating_sub(debt).0.to_raw(),
+        <test_asset_wallet::Value as AssetInfo>::MAX
+    );
+    // the smallest debt is saturated to itself and a debt is never 0
+    let smallest = debt.saturating_add(debt);
+    assert_eq!(smallest.overflowing_sub(credit), (smallest, true));
+    assert_eq!(smallest.overflowing_add(smallest), (smallest, true));
+
+    // an asset with MIN = 0 never has a debt, saturating below 0 gives Credit(0)
+    type Positive = Asset<test_asset_only_positive::Value>;
+    let positive = Positive::try_from(10).unwrap();
+    assert_eq!(Positive::try_from(-1), Err(Error::WouldGoNegative));
+    assert_eq!(
+        positive.overflowing_neg(),
+        (Positive::try_from(0).unwrap(), true)
+    );
+    assert!(matches!(
+        positive.saturating_mul(-3),
+        Asset::Credit(zero) if zero.0.to_raw() == 0
+    ));
+
+    // the primitive integers saturate like the std methods
+    let count = Asset::<u8>::try_from(200).unwrap();
+    assert_eq!(count.saturating_add(count).get_inner(), u8::MAX);
+    assert_eq!(count.saturating_mul(-1).get_inner(), 0);
+    let points = Asset::<i64>::try_from(i64::MIN as i128).unwrap();
+    assert_eq!(
+        points.overflowing_div(-1),
+        (Asset::try_from(i64::MAX as i128).unwrap(), true)
+    );
+    assert!(!points.overflowing_div(2).1);
+}
+
+#[test]
+#[should_panic(expected = "attempt to divide by zero")]
+fn saturating_div_by_zero() {
+    Asset::<i32>::try_from(1).unwrap().saturating_div(0);
+}
+
+mod legacy {
+    get_traits!();
+    new_asset!(legacy_usd, 2, 1_000_000);
+    pub type LegacyUsd = super::Asset<legacy_usd::Value>;
+}
+
+#[test]
+fn assets_share_the_fixed_of_the_crate() {
+    fn fixed_of_merx<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128>(
+        fixed: crate::fixed::Fixed<LEN, MIN, MAX, POW>,
+    ) -> crate::fixed::Fixed<LEN, MIN, MAX, POW> {
+        fixed
+    }
+    let legacy = legacy::LegacyUsd::try_from((105, 2)).unwrap();
+    let asset = Asset::<test_asset_wallet::Value>::try_from((105, 2)).unwrap();
+    assert!(fixed_of_merx(legacy.get_inner().0) == legacy.get_inner().0);
+    assert!(fixed_of_merx(asset.get_inner().0) == asset.get_inner().0);
+    assert_eq!(legacy.to_parts(), (1, 5, 100));
+    assert_eq!(legacy.to_string(), asset.to_string());
+}
+
+#[cfg(feature = "derive")]
+mod derived {
+    #[derive(crate::Asset)]
+    #[asset(decimals = 2, max = "14e12", code = "USD", symbol = "$", rounding = HalfEven)]
+    pub struct Usd;
+
+    #[derive(crate::Asset)]
+    #[asset(decimals = 2, min = -500, max = 1_000_000, tick = 5, module = "wallet")]
+    pub struct Wallet;
+
+    #[derive(crate::Asset)]
+    #[asset(decimals = 0, non_negative, rounding = Floor)]
+    pub struct Inventory;
+
+    // a run of capitals is a single word in the name of the module
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(crate::Asset)]
+    #[asset(decimals = 2, max = 1_000_000, code = "EUR")]
+    pub struct EUR;
+
+    #[derive(crate::Asset)]
+    #[asset(decimals = 6, max = 1_000_000)]
+    pub struct USDCoin;
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_assets() {
+    use super::Definition;
+    use derived::{eur, inventory, usd, usd_coin, wallet};
+
+    type Usd = Asset<<derived::Usd as Definition>::Value>;
+    let usd = Usd::try_from("14000000000000").unwrap();
+    assert_eq!(usd.to_string(), "14000000000000.00");
+    assert_eq!(
+        (usd + usd),
+        Err(Error::AboveBound {
+            max: 1_400_000_000_000_000
+        })
+    );
+    assert!(Asset::<usd::Value>::try_from(-14_000_000_000_000).is_ok());
+    assert_eq!(usd::Value::NAME, "usd");
+    assert_eq!(usd::Value::CODE, Some("USD"));
+    assert_eq!(usd::Value::SYMBOL, Some("$"));
+    assert_eq!(usd::Value::ROUNDING, RoundingMode::HalfEven);
+
+    type Wallet = Asset<wallet::Value>;
+    assert_eq!(Wallet::try_from("-500.00").unwrap().to_string(), "-500.00");
+    assert_eq!(
+        Wallet::try_from("-500.05"),
+        Err(Error::BelowBound { min: -50_000 })
+    );
+    assert_eq!(
+        Wallet::try_from("1.01"),
+        Err(Error::NotMultipleOfTick { tick: 5 })
+    );
+    assert_eq!(wallet::Value::CODE, None);
+    assert_eq!(wallet::Value::ROUNDING, RoundingMode::HalfEven);
+    assert_eq!(eur::Value::NAME, "eur");
+    assert_eq!(eur::Value::CODE, Some("EUR"));
+    assert_eq!(usd_coin::Value::NAME, "usd_coin");
+
+    type Inventory = Asset<inventory::Value>;
+    let item = Inventory::try_from(i128::MAX).unwrap();
+    assert!(Asset::Credit(item.into_credit()) == item);
+    assert_eq!(Inventory::try_from(-1), Err(Error::WouldGoNegative));
+    assert_eq!(inventory::Value::ROUNDING, RoundingMode::Floor);
+}
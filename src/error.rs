@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// The error returned by every fallible conversion and checked operation on assets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// The result does not fit in the storage used by the asset.
+    Overflow,
+    /// The result is bigger than the upper bound of the asset, `max` is expressed in minimum
+    /// units.
+    AboveBound { max: i128 },
+    /// The result is smaller than the lower bound of the asset, `min` is expressed in minimum
+    /// units.
+    BelowBound { min: i128 },
+    /// The result is negative and the lower bound of the asset is 0.
+    WouldGoNegative,
+    /// The value has more decimal digits (`given`) than the asset can represent (`supported`).
+    PrecisionLoss { given: u32, supported: u32 },
+    /// The value is a NaN or an infinite float.
+    NonFinite,
+    /// The string is not a valid decimal number, `position` is the byte offset of the problem.
+    Parse {
+        position: usize,
+        reason: &'static str,
+    },
+    /// The divisor is 0.
+    DivisionByZero,
+    /// The result can not be represented exactly and the rounding mode is
+    /// `RoundingMode::Unnecessary`.
+    Inexact,
+    /// The sign of the value does not match the kind of asset: a `Credit` can not be negative and
+    /// a `Debt` must be negative.
+    WrongSign,
+    /// The value is not a multiple of the minimum unit of the asset, `tick` is expressed in
+    /// `10^-DECIMALS` units.
+    NotMultipleOfTick { tick: i128 },
+    /// The operation mixes two `DynAsset`s with different specs, or a `DynAsset` and an `Asset`
+    /// that is not defined by the same spec.
+    DifferentAssets,
+    /// The `AssetSpec` can not define an asset.
+    InvalidSpec { reason: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Overflow => write!(f, "the result overflows the asset storage"),
+            Error::AboveBound { max } => {
+                write!(f, "the result exceeds the asset bound of {} units", max)
+            }
+            Error::BelowBound { min } => {
+                write!(f, "the result is below the asset bound of {} units", min)
+            }
+            Error::WouldGoNegative => write!(f, "the result would be negative"),
+            Error::PrecisionLoss { given, supported } => write!(
+                f,
+                "{} decimal digits given but the asset supports only {}",
+                given, supported
+            ),
+            Error::NonFinite => write!(f, "the value is not a finite number"),
+            Error::Parse { position, reason } => {
+                write!(f, "invalid decimal at byte {}: {}", position, reason)
+            }
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::Inexact => write!(f, "the result can not be represented exactly"),
+            Error::WrongSign => write!(f, "the sign of the value does not match the asset kind"),
+            Error::NotMultipleOfTick { tick } => {
+                write!(
+                    f,
+                    "the value is not a multiple of the tick of {} units",
+                    tick
+                )
+            }
+            Error::DifferentAssets => write!(f, "the values are not of the same asset"),
+            Error::InvalidSpec { reason } => write!(f, "invalid asset spec: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -1,11 +1,42 @@
+use crate::asset::factor::{float_to_raw, FloatConversion};
+use crate::asset::{pow_10, Asset, CheckedOps};
+use crate::error::Error;
+use crate::rounding::RoundingMode;
 use crate::utils::numeric_methods::*;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 
-pub enum FloatRounding {
-    Floor,
-    Ceil,
-    Round,
-    Trunc,
+/// `MAX` of the assets without an upper bound, the only limit of their value is the range of the
+/// `i128` storage.
+pub const UNBOUNDED: i128 = i128::MAX;
+
+/// `MIN` of the assets without a lower bound.
+pub const UNBOUNDED_MIN: i128 = i128::MIN;
+
+/// Return true if `value` is in `[min, max]`
+#[inline(always)]
+pub const fn is_in_bound(value: i128, min: i128, max: i128) -> bool {
+    min <= value && value <= max
+}
+
+/// The error for a value out of `[min, max]`, `negative` tells if the value is below `min` or
+/// above `max`: `Error::BelowBound`, `Error::AboveBound`, `Error::WouldGoNegative` for the assets
+/// that can not be negative or `Error::Overflow` for the assets without that bound.
+#[inline(always)]
+pub const fn out_of_bound(negative: bool, min: i128, max: i128) -> Error {
+    if negative {
+        if min == UNBOUNDED_MIN {
+            Error::Overflow
+        } else if min == 0 {
+            Error::WouldGoNegative
+        } else {
+            Error::BelowBound { min }
+        }
+    } else if max == UNBOUNDED {
+        Error::Overflow
+    } else {
+        Error::AboveBound { max }
+    }
 }
 
 pub trait ArrayWrapper<const LEN: usize>: Sized {
@@ -15,110 +46,145 @@ pub trait ArrayWrapper<const LEN: usize>: Sized {
 
 pub trait HasFixedOps<const LEN: usize>: ArrayWrapper<LEN> {
     #[inline(always)]
-    fn add_inner(self, rhs: Self) -> Option<Self> {
-        match LEN {
-            4 => Some(Self::from_array_unchecked(add_buffers_32(
-                self.get_array(),
-                rhs.get_array(),
-            )?)),
-            8 => Some(Self::from_array_unchecked(add_buffers_64(
-                self.get_array(),
-                rhs.get_array(),
-            )?)),
-            16 => Some(Self::from_array_unchecked(add_buffers_128(
-                self.get_array(),
-                rhs.get_array(),
-            )?)),
+    fn add_inner(self, rhs: Self) -> Result<Self, Error> {
+        let sum = match LEN {
+            4 => add_buffers_32(self.get_array(), rhs.get_array()),
+            8 => add_buffers_64(self.get_array(), rhs.get_array()),
+            16 => add_buffers_128(self.get_array(), rhs.get_array()),
             _ => panic!("IsFixed is implemented only fo array of len 4 8 and 16"),
-        }
+        };
+        Ok(Self::from_array_unchecked(sum.ok_or(Error::Overflow)?))
+    }
+
+    #[inline(always)]
+    fn sub_inner(self, rhs: Self) -> Result<Self, Error> {
+        let sub = match LEN {
+            4 => sub_buffers_32(self.get_array(), rhs.get_array()),
+            8 => sub_buffers_64(self.get_array(), rhs.get_array()),
+            16 => sub_buffers_128(self.get_array(), rhs.get_array()),
+            _ => panic!("IsFixed is implemented only fo array of len 4 8 and 16"),
+        };
+        Ok(Self::from_array_unchecked(sub.ok_or(Error::Overflow)?))
+    }
+
+    #[inline(always)]
+    fn neg_inner(self) -> Result<Self, Error> {
+        let neg = match LEN {
+            4 => neg_buffer_32(self.get_array()),
+            8 => neg_buffer_64(self.get_array()),
+            16 => neg_buffer_128(self.get_array()),
+            _ => panic!("IsFixed is implemented only fo array of len 4 8 and 16"),
+        };
+        Ok(Self::from_array_unchecked(neg.ok_or(Error::Overflow)?))
     }
 
+    /// Multiply for an integer, a multiplier that does not fit in the storage is not truncated:
+    /// the product is computed in an i128 and fails with `Error::Overflow` if it does not fit in
+    /// the storage.
     #[inline]
-    fn mul_inner<T: Into<i128>>(self, rhs: T) -> Option<Self> {
+    fn mul_inner<T: Into<i128>>(self, rhs: T) -> Result<Self, Error> {
         let rhs: i128 = rhs.into();
-        match LEN {
-            4 => Some(Self::from_array_unchecked(mul_buffer_32(
-                self.get_array(),
-                rhs as i32,
-            )?)),
-            8 => Some(Self::from_array_unchecked(mul_buffer_64(
-                self.get_array(),
-                rhs as i64,
-            )?)),
-            16 => Some(Self::from_array_unchecked(mul_buffer_128(
-                self.get_array(),
-                rhs as i128,
-            )?)),
+        let buf = self.get_array();
+        let mul = match LEN {
+            4 => match i32::try_from(rhs) {
+                Ok(rhs) => mul_buffer_32(buf, rhs),
+                Err(_) => mul_wide_buffer_32(buf, rhs),
+            },
+            8 => match i64::try_from(rhs) {
+                Ok(rhs) => mul_buffer_64(buf, rhs),
+                Err(_) => mul_wide_buffer_64(buf, rhs),
+            },
+            16 => mul_buffer_128(buf, rhs),
             _ => panic!("IsFixed is implemented only fo array of len 4 8 and 16"),
-        }
+        };
+        Ok(Self::from_array_unchecked(mul.ok_or(Error::Overflow)?))
     }
 
+    /// Divide for an integer rounding toward zero, a divisor that does not fit in the storage is
+    /// not truncated: the quotient is computed in an i128.
     #[inline]
-    fn div_inner<T: Into<i128>>(self, rhs: T) -> Option<Self> {
+    fn div_inner<T: Into<i128>>(self, rhs: T) -> Result<Self, Error> {
         let rhs: i128 = rhs.into();
-        match LEN {
-            4 => Some(Self::from_array_unchecked(div_buffer_32(
-                self.get_array(),
-                rhs as i32,
-            )?)),
-            8 => Some(Self::from_array_unchecked(div_buffer_64(
-                self.get_array(),
-                rhs as i64,
-            )?)),
-            16 => Some(Self::from_array_unchecked(div_buffer_128(
-                self.get_array(),
-                rhs as i128,
-            )?)),
-            _ => panic!("IsFixed is implemented only fo array of len 4 8 and 16"),
+        if rhs == 0 {
+            return Err(Error::DivisionByZero);
         }
+        let buf = self.get_array();
+        let div = match LEN {
+            4 => match i32::try_from(rhs) {
+                Ok(rhs) => div_buffer_32(buf, rhs),
+                Err(_) => div_wide_buffer_32(buf, rhs),
+            },
+            8 => match i64::try_from(rhs) {
+                Ok(rhs) => div_buffer_64(buf, rhs),
+                Err(_) => div_wide_buffer_64(buf, rhs),
+            },
+            16 => div_buffer_128(buf, rhs),
+            _ => panic!("IsFixed is implemented only fo array of len 4 8 and 16"),
+        };
+        Ok(Self::from_array_unchecked(div.ok_or(Error::Overflow)?))
     }
 }
-pub trait HasBound<const MAX: i128, const LEN: usize>: HasFixedOps<LEN> {
+pub trait HasBound<const MIN: i128, const MAX: i128, const LEN: usize>: HasFixedOps<LEN> {
     // TODO change names in checked_add ec ecc
     #[inline]
-    fn add_checked(self, rhs: Self) -> Option<Self> {
+    fn add_checked(self, rhs: Self) -> Result<Self, Error> {
         let sum = self.add_inner(rhs)?.get_array();
-        match LEN {
-            4 => buffer_is_less_or_equal_32(sum, MAX as i32)?,
-            8 => buffer_is_less_or_equal_64(sum, MAX as i64)?,
-            16 => buffer_is_less_or_equal_128(sum, MAX as i128)?,
-            _ => panic!("HasBound is implemented only fo array of len 4 8 and 16"),
-        };
-        Some(Self::from_array_unchecked(sum))
+        Self::from_raw_buffer_checked(sum)
     }
 
     #[inline]
-    fn mul_checked(self, rhs: i128) -> Option<Self> {
+    fn sub_checked(self, rhs: Self) -> Result<Self, Error> {
+        let sub = self.sub_inner(rhs)?.get_array();
+        Self::from_raw_buffer_checked(sub)
+    }
+
+    #[inline]
+    fn neg_checked(self) -> Result<Self, Error> {
+        let neg = self.neg_inner()?.get_array();
+        Self::from_raw_buffer_checked(neg)
+    }
+
+    #[inline]
+    fn mul_checked(self, rhs: i128) -> Result<Self, Error> {
         let mul = self.mul_inner(rhs)?.get_array();
-        match LEN {
-            4 => buffer_is_less_or_equal_32(mul, MAX as i32)?,
-            8 => buffer_is_less_or_equal_64(mul, MAX as i64)?,
-            16 => buffer_is_less_or_equal_128(mul, MAX as i128)?,
-            _ => panic!("HasBound is implemented only fo array of len 4 8 and 16"),
-        };
-        Some(Self::from_array_unchecked(mul))
+        Self::from_raw_buffer_checked(mul)
     }
 
     #[inline]
-    fn div_checked(self, rhs: i128) -> Option<Self> {
+    fn div_checked(self, rhs: i128) -> Result<Self, Error> {
         let div = self.div_inner(rhs)?.get_array();
-        match LEN {
-            4 => buffer_is_less_or_equal_32(div, MAX as i32)?,
-            8 => buffer_is_less_or_equal_64(div, MAX as i64)?,
-            16 => buffer_is_less_or_equal_128(div, MAX as i128)?,
-            _ => panic!("HasBound is implemented only fo array of len 4 8 and 16"),
-        };
-        Some(Self::from_array_unchecked(div))
+        Self::from_raw_buffer_checked(div)
     }
 
-    fn from_raw_buffer_checked(buf: [u8; LEN]) -> Option<Self> {
-        match LEN {
-            4 => buffer_is_less_or_equal_32(buf, MAX as i32)?,
-            8 => buffer_is_less_or_equal_64(buf, MAX as i64)?,
-            16 => buffer_is_less_or_equal_128(buf, MAX as i128)?,
+    /// Build a value from an amount expressed in minimum units
+    #[inline]
+    fn from_raw_checked(raw: i128) -> Result<Self, Error> {
+        if is_in_bound(raw, MIN, MAX) {
+            let buf = <[u8; LEN]>::try_from(&raw.to_le_bytes()[0..LEN]).unwrap();
+            Ok(Self::from_array_unchecked(buf))
+        } else {
+            Err(out_of_bound(raw < 0, MIN, MAX))
+        }
+    }
+
+    #[inline]
+    fn from_raw_buffer_checked(buf: [u8; LEN]) -> Result<Self, Error> {
+        // MIN and MAX are constants so for the unbounded assets the check is optimized away and
+        // only the overflow checks of the operations are left
+        if MIN == UNBOUNDED_MIN && MAX == UNBOUNDED {
+            return Ok(Self::from_array_unchecked(buf));
+        }
+        let in_range = match LEN {
+            4 => buffer_is_in_range_32(buf, MIN as i32, MAX as i32),
+            8 => buffer_is_in_range_64(buf, MIN as i64, MAX as i64),
+            16 => buffer_is_in_range_128(buf, MIN, MAX),
             _ => panic!("Fixed is implemented only fo array of len 4 8 and 16"),
         };
-        Some(Self::from_array_unchecked(buf))
+        match in_range {
+            Ordering::Equal => Ok(Self::from_array_unchecked(buf)),
+            Ordering::Less => Err(out_of_bound(true, MIN, MAX)),
+            Ordering::Greater => Err(out_of_bound(false, MIN, MAX)),
+        }
     }
 }
 
@@ -137,12 +203,23 @@ pub trait IsFixed<const LEN: usize, const MAX: i128, const POW: u128>:
             }
             16 => {
                 let (int, frac, p) = buffer_to_parts_128::<LEN, POW>(self.get_array());
-                (int as i128, frac as i128, p)
+                (int, frac, p)
             }
             _ => panic!("impossible state"),
         }
     }
 
+    /// Return the value expressed in minimum units
+    fn to_raw(self) -> i128 {
+        match LEN {
+            4 => buffer_to_int_32(self.get_array()) as i128,
+            8 => buffer_to_int_64(self.get_array()) as i128,
+            16 => buffer_to_int_128(self.get_array()),
+            _ => panic!("impossible state"),
+        }
+    }
+
+    #[allow(clippy::wrong_self_convention)]
     fn is_positive(self) -> bool {
         match LEN {
             4 => buffer_is_positive_32(self.get_array()),
@@ -153,237 +230,331 @@ pub trait IsFixed<const LEN: usize, const MAX: i128, const POW: u128>:
     }
 }
 
+/// `ByteArray` and `Fixed` are defined in this module, the macro only imports them, so that the
+/// crates that still call it compile.
 #[macro_export]
 macro_rules! get_fixed {
     () => {
+        #[allow(unused_imports)]
         use std::convert::TryFrom;
-        use $crate::fixed::{ArrayWrapper, FloatRounding, HasBound, HasFixedOps, IsFixed};
-        use $crate::utils::numeric_methods::*;
-
-        const I32_LEN: usize = 4;
-        const I64_LEN: usize = 8;
-        const I128_LEN: usize = 16;
-
-        #[derive(Copy, Clone)]
-        #[repr(align(8))]
-        pub struct ByteArray<const LEN: usize>([u8; LEN]);
-
-        macro_rules! int_partial_eq {
-            ($len:ident, $impl_:ident) => {
-                impl PartialEq for ByteArray<$len> {
-                    fn eq(&self, rhs: &Self) -> bool {
-                        if $impl_(self.0, rhs.0) == std::cmp::Ordering::Equal {
-                            true
-                        } else {
-                            false
-                        }
-                    }
+        #[allow(unused_imports)]
+        use $crate::fixed::{ArrayWrapper, ByteArray, Fixed, HasBound, HasFixedOps, IsFixed};
+    };
+}
+
+const I32_LEN: usize = 4;
+const I64_LEN: usize = 8;
+const I128_LEN: usize = 16;
+
+/// The little endian bytes of a 4, 8 or 16 bytes integer
+#[derive(Copy, Clone)]
+#[repr(align(8))]
+pub struct ByteArray<const LEN: usize>([u8; LEN]);
+
+macro_rules! int_partial_eq {
+    ($len:ident, $impl_:ident) => {
+        impl PartialEq for ByteArray<$len> {
+            fn eq(&self, rhs: &Self) -> bool {
+                if $impl_(self.0, rhs.0) == std::cmp::Ordering::Equal {
+                    true
+                } else {
+                    false
                 }
-            };
+            }
         }
+    };
+}
 
-        int_partial_eq!(I32_LEN, ord_32);
-        int_partial_eq!(I64_LEN, ord_64);
-        int_partial_eq!(I128_LEN, ord_128);
+int_partial_eq!(I32_LEN, ord_32);
+int_partial_eq!(I64_LEN, ord_64);
+int_partial_eq!(I128_LEN, ord_128);
 
-        macro_rules! int_partial_ord {
-            ($len:ident, $impl_:ident) => {
-                impl PartialOrd for ByteArray<$len> {
-                    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
-                        Some($impl_(self.0, rhs.0))
-                    }
-                }
-            };
+macro_rules! int_partial_ord {
+    ($len:ident, $impl_:ident) => {
+        impl PartialOrd for ByteArray<$len> {
+            fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(rhs))
+            }
         }
+    };
+}
 
-        int_partial_ord!(I32_LEN, ord_32);
-        int_partial_ord!(I64_LEN, ord_64);
-        int_partial_ord!(I128_LEN, ord_128);
-
-        impl Eq for ByteArray<I32_LEN> {}
-        impl Eq for ByteArray<I64_LEN> {}
-        impl Eq for ByteArray<I128_LEN> {}
-
-        macro_rules! int_try_from {
-            ($len:ident, $ty_:ty) => {
-                impl TryFrom<i128> for ByteArray<$len> {
-                    type Error = ();
-
-                    fn try_from(value: i128) -> Result<ByteArray<$len>, Self::Error> {
-                        if value <= <$ty_>::max_value() as i128
-                            && value >= <$ty_>::min_value() as i128
-                        {
-                            return Ok(Self::from(value as $ty_));
-                        } else {
-                            return Err(());
-                        }
-                    }
-                }
-            };
-        }
+int_partial_ord!(I32_LEN, ord_32);
+int_partial_ord!(I64_LEN, ord_64);
+int_partial_ord!(I128_LEN, ord_128);
 
-        int_try_from!(I32_LEN, i32);
-        int_try_from!(I64_LEN, i64);
+impl Eq for ByteArray<I32_LEN> {}
+impl Eq for ByteArray<I64_LEN> {}
+impl Eq for ByteArray<I128_LEN> {}
 
-        macro_rules! int_from {
-            ($len:ident, $ty_:ty) => {
-                impl From<$ty_> for ByteArray<$len> {
-                    fn from(value: $ty_) -> ByteArray<$len> {
-                        let inner = <[u8; $len]>::try_from(&value.to_le_bytes()[0..$len]).unwrap();
-                        ByteArray(inner)
-                    }
-                }
-            };
+macro_rules! int_ord {
+    ($len:ident, $impl_:ident) => {
+        impl Ord for ByteArray<$len> {
+            fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+                $impl_(self.0, rhs.0)
+            }
         }
-        int_from!(I32_LEN, i32);
-        int_from!(I64_LEN, i64);
-        int_from!(I128_LEN, i128);
-
-        macro_rules! int_into {
-            ($len:ident, $ty_:ty) => {
-                impl Into<$ty_> for ByteArray<$len> {
-                    fn into(self) -> $ty_ {
-                        let int = <$ty_>::from_le_bytes(self.0);
-                        int
-                    }
+    };
+}
+
+int_ord!(I32_LEN, ord_32);
+int_ord!(I64_LEN, ord_64);
+int_ord!(I128_LEN, ord_128);
+
+// Two arrays of the same len are equal only if they have the same bytes
+impl<const LEN: usize> std::hash::Hash for ByteArray<LEN> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+macro_rules! int_try_from {
+    ($len:ident, $ty_:ty) => {
+        impl TryFrom<i128> for ByteArray<$len> {
+            type Error = Error;
+
+            fn try_from(value: i128) -> Result<ByteArray<$len>, Self::Error> {
+                if value <= <$ty_>::MAX as i128 && value >= <$ty_>::MIN as i128 {
+                    return Ok(Self::from(value as $ty_));
+                } else {
+                    return Err(Error::Overflow);
                 }
-            };
+            }
         }
-        int_into!(I32_LEN, i32);
-        int_into!(I64_LEN, i64);
-        int_into!(I128_LEN, i128);
-
-        impl<const LEN: usize> ArrayWrapper<LEN> for ByteArray<LEN> {
-            #[inline(always)]
-            fn get_array(self) -> [u8; LEN] {
-                self.0
+    };
+}
+
+int_try_from!(I32_LEN, i32);
+int_try_from!(I64_LEN, i64);
+
+macro_rules! int_from {
+    ($len:ident, $ty_:ty) => {
+        impl From<$ty_> for ByteArray<$len> {
+            fn from(value: $ty_) -> ByteArray<$len> {
+                let inner = <[u8; $len]>::try_from(&value.to_le_bytes()[0..$len]).unwrap();
+                ByteArray(inner)
             }
-            #[inline]
-            fn from_array_unchecked(buf: [u8; LEN]) -> Self {
-                ByteArray(buf)
+        }
+    };
+}
+int_from!(I32_LEN, i32);
+int_from!(I64_LEN, i64);
+int_from!(I128_LEN, i128);
+
+macro_rules! int_into {
+    ($len:ident, $ty_:ty) => {
+        impl From<ByteArray<$len>> for $ty_ {
+            fn from(array: ByteArray<$len>) -> $ty_ {
+                <$ty_>::from_le_bytes(array.0)
             }
         }
+    };
+}
+int_into!(I32_LEN, i32);
+int_into!(I64_LEN, i64);
+int_into!(I128_LEN, i128);
 
-        #[derive(Copy, Clone)]
-        #[repr(align(8))]
-        pub struct Fixed<const LEN: usize, const MAX: i128, const POW: u128>(ByteArray<LEN>);
+impl<const LEN: usize> ArrayWrapper<LEN> for ByteArray<LEN> {
+    #[inline(always)]
+    fn get_array(self) -> [u8; LEN] {
+        self.0
+    }
+    #[inline]
+    fn from_array_unchecked(buf: [u8; LEN]) -> Self {
+        ByteArray(buf)
+    }
+}
 
-        macro_rules! fixed_part_eq {
-            ($len:ident) => {
-                impl<const MAX: i128, const POW: u128> PartialEq for Fixed<$len, MAX, POW> {
-                    fn eq(&self, rhs: &Self) -> bool {
-                        self.0 == rhs.0
-                    }
-                }
-            };
-        }
-        fixed_part_eq!(I32_LEN);
-        fixed_part_eq!(I64_LEN);
-        fixed_part_eq!(I128_LEN);
-
-        // try_from is implemented just for i128 so is impossible to loose precision when Fixed is constructed
-        macro_rules! fixed_try_from {
-            ($len:ident, $ty_:ty) => {
-                impl<const MAX: i128, const POW: u128> TryFrom<i128> for Fixed<$len, MAX, POW> {
-                    type Error = ();
-
-                    fn try_from(value: i128) -> Result<Fixed<$len, MAX, POW>, Self::Error> {
-                        debug_assert!(MAX <= <$ty_>::max_value() as i128);
-                        let value = value.checked_mul(POW as i128).ok_or(())?;
-                        if value.checked_abs().ok_or(())? <= MAX {
-                            Ok(Fixed(ByteArray::from(value as $ty_)))
-                        } else {
-                            Err(())
-                        }
-                    }
-                }
-            };
+/// A fixed point value with `log10(POW)` decimal digits in `[MIN, MAX]`, stored in the `LEN` bytes
+/// of a `ByteArray`. `new_asset!` wraps a `Fixed` in the `Value` of the asset, so assets defined in
+/// different crates share the same inner type.
+#[derive(Copy, Clone)]
+#[repr(align(8))]
+pub struct Fixed<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128>(
+    ByteArray<LEN>,
+);
+
+macro_rules! fixed_part_eq {
+    ($len:ident) => {
+        impl<const MIN: i128, const MAX: i128, const POW: u128> PartialEq
+            for Fixed<$len, MIN, MAX, POW>
+        {
+            fn eq(&self, rhs: &Self) -> bool {
+                self.0 == rhs.0
+            }
         }
-        fixed_try_from!(I32_LEN, i32);
-        fixed_try_from!(I64_LEN, i64);
-        fixed_try_from!(I128_LEN, i128);
+    };
+}
+fixed_part_eq!(I32_LEN);
+fixed_part_eq!(I64_LEN);
+fixed_part_eq!(I128_LEN);
+
+macro_rules! fixed_ord {
+    ($len:ident) => {
+        impl<const MIN: i128, const MAX: i128, const POW: u128> Eq for Fixed<$len, MIN, MAX, POW> {}
 
-        impl<const LEN: usize, const MAX: i128, const POW: u128> ArrayWrapper<LEN>
-            for Fixed<LEN, MAX, POW>
+        impl<const MIN: i128, const MAX: i128, const POW: u128> PartialOrd
+            for Fixed<$len, MIN, MAX, POW>
         {
-            #[inline]
-            fn get_array(self) -> [u8; LEN] {
-                let inner = self.0;
-                inner.0
+            fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(rhs))
             }
-            #[inline]
-            fn from_array_unchecked(buf: [u8; LEN]) -> Self {
-                Fixed(ByteArray(buf))
+        }
+
+        impl<const MIN: i128, const MAX: i128, const POW: u128> Ord for Fixed<$len, MIN, MAX, POW> {
+            fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&rhs.0)
             }
         }
+    };
+}
+fixed_ord!(I32_LEN);
+fixed_ord!(I64_LEN);
+fixed_ord!(I128_LEN);
 
-        impl<const LEN: usize, const MAX: i128, const POW: u128> HasFixedOps<LEN>
-            for Fixed<LEN, MAX, POW>
+impl<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128> std::hash::Hash
+    for Fixed<LEN, MIN, MAX, POW>
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+// try_from is implemented just for i128 so is impossible to loose precision when Fixed is constructed
+macro_rules! fixed_try_from {
+    ($len:ident, $ty_:ty) => {
+        impl<const MIN: i128, const MAX: i128, const POW: u128> TryFrom<i128>
+            for Fixed<$len, MIN, MAX, POW>
         {
+            type Error = Error;
+
+            fn try_from(value: i128) -> Result<Fixed<$len, MIN, MAX, POW>, Self::Error> {
+                debug_assert!(MAX <= <$ty_>::MAX as i128);
+                debug_assert!(MIN >= <$ty_>::MIN as i128);
+                let out_of_bound = out_of_bound(value < 0, MIN, MAX);
+                let value = value.checked_mul(POW as i128).ok_or(out_of_bound)?;
+                if is_in_bound(value, MIN, MAX) {
+                    Ok(Fixed(ByteArray::from(value as $ty_)))
+                } else {
+                    Err(out_of_bound)
+                }
+            }
         }
+    };
+}
+fixed_try_from!(I32_LEN, i32);
+fixed_try_from!(I64_LEN, i64);
+fixed_try_from!(I128_LEN, i128);
+
+impl<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128> ArrayWrapper<LEN>
+    for Fixed<LEN, MIN, MAX, POW>
+{
+    #[inline]
+    fn get_array(self) -> [u8; LEN] {
+        let inner = self.0;
+        inner.0
+    }
+    #[inline]
+    fn from_array_unchecked(buf: [u8; LEN]) -> Self {
+        Fixed(ByteArray(buf))
+    }
+}
+
+impl<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128> HasFixedOps<LEN>
+    for Fixed<LEN, MIN, MAX, POW>
+{
+}
+
+impl<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128> HasBound<MIN, MAX, LEN>
+    for Fixed<LEN, MIN, MAX, POW>
+{
+}
 
-        // Fixed from (decimal_int, decimal_exp) for example if the Fixed has precision = 2
-        // (45, 2) -> 0.45    --> 45
-        // (45, 1) -> 4.5     --> 450
-        // (45, 0) -> 45      --> 4500
-        // (405, 1) -> 40.5   --> 4050
-        // (405, 2) -> 4.05   --> 405
-        // (405, 3) -> None
-        macro_rules! fixed_try_from_parts {
-            ($len:ident) => {
-                impl<const MAX: i128, const POW: u128> TryFrom<(i128, u128)>
-                    for Fixed<$len, MAX, POW>
-                {
-                    type Error = ();
-
-                    fn try_from(value: (i128, u128)) -> Result<Fixed<$len, MAX, POW>, Self::Error> {
-                        let precision = value.1;
-                        let value = value.0;
-                        let self_precision = f64::log10(POW as f64) as u128;
-                        if precision > self_precision {
-                            return Err(());
-                        }
-                        let normalized_pow = 10_u128.pow(self_precision as u32 - precision as u32);
-                        let normalized_val = value * normalized_pow as i128;
-                        if normalized_val <= MAX {
-                            let buf =
-                                <[u8; $len]>::try_from(&normalized_val.to_le_bytes()[0..$len])
-                                    .unwrap();
-                            Ok(Self::from_array_unchecked(buf))
-                        } else {
-                            Err(())
-                        }
-                    }
+impl<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128> IsFixed<LEN, MAX, POW>
+    for Fixed<LEN, MIN, MAX, POW>
+{
+}
+
+// Fixed from (decimal_int, decimal_exp) for example if the Fixed has precision = 2
+// (45, 2) -> 0.45    --> 45
+// (45, 1) -> 4.5     --> 450
+// (45, 0) -> 45      --> 4500
+// (405, 1) -> 40.5   --> 4050
+// (405, 2) -> 4.05   --> 405
+// (405, 3) -> None
+macro_rules! fixed_try_from_parts {
+    ($len:ident) => {
+        impl<const MIN: i128, const MAX: i128, const POW: u128> TryFrom<(i128, u128)>
+            for Fixed<$len, MIN, MAX, POW>
+        {
+            type Error = Error;
+
+            fn try_from(value: (i128, u128)) -> Result<Fixed<$len, MIN, MAX, POW>, Self::Error> {
+                let precision = value.1;
+                let value = value.0;
+                let self_precision = f64::log10(POW as f64) as u128;
+                if precision > self_precision {
+                    return Err(Error::PrecisionLoss {
+                        given: u32::try_from(precision).unwrap_or(u32::MAX),
+                        supported: self_precision as u32,
+                    });
                 }
-            };
+                let out_of_bound = out_of_bound(value < 0, MIN, MAX);
+                let normalized_pow = 10_u128.pow(self_precision as u32 - precision as u32);
+                let normalized_val = value
+                    .checked_mul(normalized_pow as i128)
+                    .ok_or(out_of_bound)?;
+                if is_in_bound(normalized_val, MIN, MAX) {
+                    let buf =
+                        <[u8; $len]>::try_from(&normalized_val.to_le_bytes()[0..$len]).unwrap();
+                    Ok(Self::from_array_unchecked(buf))
+                } else {
+                    Err(out_of_bound)
+                }
+            }
         }
+    };
+}
+
+fixed_try_from_parts!(I32_LEN);
+fixed_try_from_parts!(I64_LEN);
+fixed_try_from_parts!(I128_LEN);
 
-        fixed_try_from_parts!(I32_LEN);
-        fixed_try_from_parts!(I64_LEN);
-        fixed_try_from_parts!(I128_LEN);
-
-        macro_rules! fixed_try_from_float_rounded {
-            ($len:ident) => {
-                impl<const MAX: i128, const POW: u128> TryFrom<(f64, FloatRounding)>
-                    for Fixed<$len, MAX, POW>
-                {
-                    type Error = ();
-
-                    fn try_from(
-                        value: (f64, FloatRounding),
-                    ) -> Result<Fixed<$len, MAX, POW>, Self::Error> {
-                        let frac = (POW as f64).log10() as u8;
-                        let value =
-                            checked_int_from_f64(MAX as u128, frac, value.0, value.1).ok_or(())?;
-                        let buf = <[u8; $len]>::try_from(&value.to_le_bytes()[0..$len]).unwrap();
-                        Ok(Self::from_array_unchecked(buf))
-                    }
+// The float is taken as the shortest decimal that represents it and rounded once, the bound is
+// checked on the rounded integer
+impl<const LEN: usize, const MIN: i128, const MAX: i128, const POW: u128>
+    TryFrom<(f64, RoundingMode)> for Fixed<LEN, MIN, MAX, POW>
+{
+    type Error = Error;
+
+    fn try_from(value: (f64, RoundingMode)) -> Result<Fixed<LEN, MIN, MAX, POW>, Self::Error> {
+        let (value, rounding) = value;
+        let raw =
+            float_to_raw(value, POW, FloatConversion::Shortest, rounding, 1).map_err(|error| {
+                match error {
+                    Error::Overflow => out_of_bound(value < 0.0, MIN, MAX),
+                    error => error,
                 }
-            };
-        }
+            })?;
+        Self::from_raw_checked(raw)
+    }
+}
 
-        fixed_try_from_float_rounded!(I32_LEN);
-        fixed_try_from_float_rounded!(I64_LEN);
-        fixed_try_from_float_rounded!(I128_LEN);
-    };
+pub trait FixedToInt {
+    /// Return the integer part, the fractional part and `10^decimals`
+    fn to_parts(self) -> (i128, i128, u128);
+
+    fn to_int(self) -> i128;
+}
+
+impl<T: CheckedOps> FixedToInt for Asset<T> {
+    fn to_parts(self) -> (i128, i128, u128) {
+        let pow = pow_10(T::DECIMALS as u8);
+        let raw = self.get_inner().to_raw();
+        (raw / pow as i128, raw % pow as i128, pow)
+    }
+
+    fn to_int(self) -> i128 {
+        self.to_parts().0
+    }
 }
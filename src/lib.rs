@@ -1,7 +1,3 @@
-#![feature(const_fn)]
-#![feature(const_panic)]
-#![feature(const_generics)]
-#![allow(incomplete_features)]
 #![allow(unused_imports)]
 #![allow(dead_code)]
 
@@ -9,12 +5,31 @@
 #[macro_use(quickcheck)]
 extern crate quickcheck_macros;
 
+// The code generated by `#[derive(Asset)]` refers to `::merx`
+#[cfg(all(test, feature = "derive"))]
+extern crate self as merx;
+
+pub mod error;
+
 #[macro_use]
 pub mod fixed;
 
 #[macro_use]
 pub mod asset;
 
+pub mod rounding;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use ::serde as __serde;
+
 pub mod utils;
 
-pub use asset::{Asset, Credit, Debt};
+pub use asset::{Asset, Credit, Debt, DynAsset};
+pub use error::Error;
+#[cfg(feature = "derive")]
+pub use merx_derive::Asset;
+pub use rounding::RoundingMode;
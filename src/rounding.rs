@@ -0,0 +1,117 @@
+use crate::error::Error;
+
+/// How a value that can not be represented exactly is rounded to the nearest representable one.
+///
+/// It is used by every lossy operation: construction from floats, parsing of strings with more
+/// decimal digits than the asset, division, conversion and rescaling.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// To the nearest value, ties to the even one (banker's rounding): 1.25 -> 1.2, 1.35 -> 1.4
+    HalfEven,
+    /// To the nearest value, ties toward positive infinity: 1.25 -> 1.3, -1.25 -> -1.2
+    HalfUp,
+    /// To the nearest value, ties toward negative infinity: 1.25 -> 1.2, -1.25 -> -1.3
+    HalfDown,
+    /// To the nearest value, ties toward zero: 1.25 -> 1.2, -1.25 -> -1.2
+    HalfTowardZero,
+    /// To the nearest value, ties away from zero: 1.25 -> 1.3, -1.25 -> -1.3
+    HalfAwayFromZero,
+    /// 1.21 -> 1.3, -1.21 -> -1.3
+    AwayFromZero,
+    /// Drop the extra digits: 1.29 -> 1.2, -1.29 -> -1.2
+    TowardZero,
+    /// Toward negative infinity: 1.29 -> 1.2, -1.21 -> -1.3
+    Floor,
+    /// Toward positive infinity: 1.21 -> 1.3, -1.29 -> -1.2
+    Ceil,
+    /// Do not round, fail with `Error::Inexact` if the value can not be represented exactly
+    Unnecessary,
+}
+
+/// The part of a value that is discarded when it is rounded, compared with half of the minimum
+/// unit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Fraction {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+impl Fraction {
+    /// Classify `remainder / divisor`, `remainder` must be smaller than `divisor`
+    #[inline]
+    pub(crate) fn from_remainder(remainder: u128, divisor: u128) -> Self {
+        if remainder == 0 {
+            return Fraction::Zero;
+        }
+        let complement = divisor - remainder;
+        if remainder < complement {
+            Fraction::BelowHalf
+        } else if remainder == complement {
+            Fraction::Half
+        } else {
+            Fraction::AboveHalf
+        }
+    }
+
+    /// Classify `(remainder + f) / divisor` where `f` is the fraction of one unit classified by
+    /// `self`, `remainder` must be smaller than `divisor`
+    #[inline]
+    pub(crate) fn carry(self, remainder: u128, divisor: u128) -> Self {
+        if self == Fraction::Zero {
+            return Fraction::from_remainder(remainder, divisor);
+        }
+        // compare remainder + f with divisor - remainder - f, with 0 < f < 1
+        let complement = divisor - remainder;
+        if remainder >= complement {
+            Fraction::AboveHalf
+        } else if complement - remainder >= 2 {
+            Fraction::BelowHalf
+        } else {
+            self
+        }
+    }
+}
+
+impl RoundingMode {
+    /// Return true if the magnitude of a truncated value must be incremented by one unit, `odd`
+    /// tells if the truncated magnitude is odd.
+    #[inline]
+    pub(crate) fn increments(
+        self,
+        negative: bool,
+        odd: bool,
+        fraction: Fraction,
+    ) -> Result<bool, Error> {
+        if fraction == Fraction::Zero {
+            return Ok(false);
+        }
+        if let Some(increments) = self.directed(negative) {
+            return Ok(increments);
+        }
+        match (self, fraction) {
+            (RoundingMode::Unnecessary, _) => Err(Error::Inexact),
+            (_, Fraction::BelowHalf) => Ok(false),
+            (_, Fraction::AboveHalf) => Ok(true),
+            (RoundingMode::HalfEven, _) => Ok(odd),
+            (RoundingMode::HalfUp, _) => Ok(!negative),
+            (RoundingMode::HalfDown, _) => Ok(negative),
+            (RoundingMode::HalfTowardZero, _) => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// For the modes that do not round to the nearest value, return if any discarded fraction
+    /// increments the magnitude.
+    #[inline]
+    fn directed(self, negative: bool) -> Option<bool> {
+        match self {
+            RoundingMode::AwayFromZero => Some(true),
+            RoundingMode::TowardZero => Some(false),
+            RoundingMode::Floor => Some(negative),
+            RoundingMode::Ceil => Some(!negative),
+            _ => None,
+        }
+    }
+}
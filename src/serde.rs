@@ -0,0 +1,323 @@
+//! Serialization and deserialization of assets, enabled by the `serde` feature.
+//!
+//! `Asset`, `Credit`, `Debt` and the `Value` generated by `new_asset!` are serialized as an exact
+//! decimal string like `"-10.50"`, so that no precision is lost in formats like JSON that store
+//! numbers as floats. The other representations can be selected with `#[serde(with = "...")]`:
+//!
+//! * [`decimal`](decimal/index.html): `"-10.50"`, the default
+//! * [`units`](units/index.html): the integer count of minimum units, `-1050`
+//! * [`object`](object/index.html): `{ "amount": "-10.50", "asset": "usd" }`
+//!
+//! Deserialization never rounds: a value with more decimal digits than the asset, bigger than the
+//! bound of the asset, negative for a `Credit` or not negative for a `Debt` is an error.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate merx;
+//! use merx::{Asset, Credit};
+//! use serde::{Deserialize, Serialize};
+//!
+//! new_asset!(usd, 2, 14_000_000_000_000);
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Account {
+//!     balance: Asset<usd::Value>,
+//!     #[serde(with = "merx::serde::units")]
+//!     limit: Credit<usd::Value>,
+//! }
+//! # fn main() {}
+//! ```
+use crate::asset::{Asset, AssetInfo, CheckedOps, Credit, Debt};
+use crate::error::Error;
+use crate::rounding::RoundingMode;
+use crate::utils::numeric_methods::fmt_decimal;
+use crate::utils::parse::parse_decimal;
+use ::serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeStruct, Serializer};
+use ::serde::Deserialize;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A type that can be serialized with the representations of this module: `Asset`, `Credit` and
+/// `Debt`.
+pub trait Amount: Sized {
+    type Value: CheckedOps;
+
+    /// Return the amount expressed in minimum units
+    fn raw(&self) -> i128;
+
+    /// Build the amount from minimum units, checking the bound of the asset and the sign
+    fn from_raw(raw: i128) -> Result<Self, Error>;
+}
+
+impl<T: CheckedOps> Amount for Asset<T> {
+    type Value = T;
+
+    fn raw(&self) -> i128 {
+        match self {
+            Asset::Credit(credit) => credit.0.to_raw(),
+            Asset::Debt(debt) => debt.0.to_raw(),
+        }
+    }
+
+    fn from_raw(raw: i128) -> Result<Self, Error> {
+        Ok(Asset::from_inner(T::from_raw_checked(raw)?))
+    }
+}
+
+impl<T: CheckedOps> Amount for Credit<T> {
+    type Value = T;
+
+    fn raw(&self) -> i128 {
+        self.0.to_raw()
+    }
+
+    fn from_raw(raw: i128) -> Result<Self, Error> {
+        if raw < 0 {
+            return Err(Error::WrongSign);
+        }
+        Ok(Credit(T::from_raw_checked(raw)?))
+    }
+}
+
+impl<T: CheckedOps> Amount for Debt<T> {
+    type Value = T;
+
+    fn raw(&self) -> i128 {
+        self.0.to_raw()
+    }
+
+    // 0 is a `Credit`, a serialized `Debt` is never 0
+    fn from_raw(raw: i128) -> Result<Self, Error> {
+        if raw >= 0 {
+            return Err(Error::WrongSign);
+        }
+        Ok(Debt(T::from_raw_checked(raw)?))
+    }
+}
+
+/// Minimum units printed as a decimal with `decimals` digits
+struct Decimal {
+    raw: i128,
+    decimals: u32,
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_decimal(f, self.raw, self.decimals)
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Parse a decimal string without rounding it
+fn parse_exact<A: Amount>(value: &str) -> Result<A, Error> {
+    let (mantissa, scale) = parse_decimal(value)?;
+    let supported = A::Value::DECIMALS;
+    if scale > supported {
+        return Err(Error::PrecisionLoss {
+            given: scale,
+            supported,
+        });
+    }
+    let raw =
+        Asset::<A::Value>::from_decimal_rounded((mantissa, scale), RoundingMode::Unnecessary)?
+            .raw();
+    A::from_raw(raw)
+}
+
+/// The amount as an exact decimal string: `"-10.50"`. This is the default representation.
+pub mod decimal {
+    use super::*;
+
+    pub fn serialize<A: Amount, S: Serializer>(
+        amount: &A,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Decimal {
+            raw: amount.raw(),
+            decimals: A::Value::DECIMALS,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, A: Amount, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<A, D::Error> {
+        deserializer.deserialize_str(DecimalVisitor(PhantomData))
+    }
+
+    struct DecimalVisitor<A>(PhantomData<A>);
+
+    impl<'de, A: Amount> Visitor<'de> for DecimalVisitor<A> {
+        type Value = A;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "a decimal string with at most {} decimal digits",
+                A::Value::DECIMALS
+            )
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<A, E> {
+            parse_exact(value).map_err(E::custom)
+        }
+    }
+}
+
+/// The amount as an integer count of minimum units: `-1050` for `-10.50` USD. Amounts that do not
+/// fit in an `i64` are serialized as `i128`.
+pub mod units {
+    use super::*;
+    use std::convert::TryFrom;
+
+    pub fn serialize<A: Amount, S: Serializer>(
+        amount: &A,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let raw = amount.raw();
+        match i64::try_from(raw) {
+            Ok(raw) => serializer.serialize_i64(raw),
+            Err(_) => serializer.serialize_i128(raw),
+        }
+    }
+
+    pub fn deserialize<'de, A: Amount, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<A, D::Error> {
+        deserializer.deserialize_i128(UnitsVisitor(PhantomData))
+    }
+
+    struct UnitsVisitor<A>(PhantomData<A>);
+
+    impl<'de, A: Amount> Visitor<'de> for UnitsVisitor<A> {
+        type Value = A;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an integer number of minimum units")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<A, E> {
+            self.visit_i128(value as i128)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<A, E> {
+            self.visit_i128(value as i128)
+        }
+
+        fn visit_i128<E: de::Error>(self, value: i128) -> Result<A, E> {
+            A::from_raw(value).map_err(E::custom)
+        }
+
+        fn visit_u128<E: de::Error>(self, value: u128) -> Result<A, E> {
+            let value = i128::try_from(value).map_err(|_| E::custom(Error::Overflow))?;
+            self.visit_i128(value)
+        }
+    }
+}
+
+/// The amount and the name of the asset: `{ "amount": "-10.50", "asset": "usd" }`. Deserializing
+/// an object with the name of another asset is an error.
+pub mod object {
+    use super::*;
+
+    const FIELDS: &[&str] = &["amount", "asset"];
+
+    pub fn serialize<A: Amount, S: Serializer>(
+        amount: &A,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut object = serializer.serialize_struct("Asset", 2)?;
+        object.serialize_field(
+            "amount",
+            &Decimal {
+                raw: amount.raw(),
+                decimals: A::Value::DECIMALS,
+            },
+        )?;
+        object.serialize_field("asset", A::Value::NAME)?;
+        object.end()
+    }
+
+    pub fn deserialize<'de, A: Amount, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<A, D::Error> {
+        deserializer.deserialize_struct("Asset", FIELDS, ObjectVisitor(PhantomData))
+    }
+
+    struct ObjectVisitor<A>(PhantomData<A>);
+
+    impl<A: Amount> ObjectVisitor<A> {
+        fn build<E: de::Error>(amount: Option<String>, asset: Option<String>) -> Result<A, E> {
+            let amount = amount.ok_or_else(|| E::missing_field("amount"))?;
+            let asset = asset.ok_or_else(|| E::missing_field("asset"))?;
+            if asset != A::Value::NAME {
+                return Err(E::invalid_value(
+                    de::Unexpected::Str(&asset),
+                    &A::Value::NAME,
+                ));
+            }
+            parse_exact(&amount).map_err(E::custom)
+        }
+    }
+
+    impl<'de, A: Amount> Visitor<'de> for ObjectVisitor<A> {
+        type Value = A;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "an object with the amount and the name of a {} asset",
+                A::Value::NAME
+            )
+        }
+
+        fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<A, S::Error> {
+            let amount = seq.next_element()?;
+            let asset = seq.next_element()?;
+            Self::build(amount, asset)
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<A, M::Error> {
+            let mut amount = None;
+            let mut asset = None;
+            while let Some(key) = map.next_key::<String>()? {
+                let field = match key.as_str() {
+                    "amount" => &mut amount,
+                    "asset" => &mut asset,
+                    _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                };
+                if field.is_some() {
+                    return Err(de::Error::custom(format!("duplicate field `{}`", key)));
+                }
+                *field = Some(map.next_value()?);
+            }
+            Self::build(amount, asset)
+        }
+    }
+}
+
+macro_rules! impl_serde {
+    ($kind:ident) => {
+        impl<T: CheckedOps> Serialize for $kind<T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                decimal::serialize(self, serializer)
+            }
+        }
+
+        impl<'de, T: CheckedOps> Deserialize<'de> for $kind<T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                decimal::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+impl_serde!(Asset);
+impl_serde!(Credit);
+impl_serde!(Debt);
@@ -1 +1,3 @@
 pub mod numeric_methods;
+pub mod parse;
+pub(crate) mod wide;
@@ -1,58 +1,66 @@
-use crate::fixed::FloatRounding;
 use std::convert::TryFrom;
+use std::fmt;
 
-pub fn checked_int_from_f64(
-    max_val: u128,
-    precision: u8,
-    val: f64,
-    rounding: FloatRounding,
-) -> Option<i128> {
-    if !val.is_finite() {
-        return None;
+/// Write `value` (expressed in minimum units) as a decimal number with `frac` decimal digits.
+///
+/// The sign, width, fill and `0` flags of the formatter are honoured. When a precision is
+/// requested (`{:.N}`) the value is padded with zeros or rounded half away from zero to N decimal
+/// digits.
+pub fn fmt_decimal(f: &mut fmt::Formatter<'_>, value: i128, frac: u32) -> fmt::Result {
+    // wrapping_abs of i128::MIN is i128::MIN that as u128 is its absolute value
+    let mut magnitude = value.wrapping_abs() as u128;
+    let mut digits = frac;
+    if let Some(precision) = f.precision() {
+        let precision = precision.min(u32::MAX as usize) as u32;
+        if precision < frac {
+            let divisor = 10_u128.pow(frac - precision);
+            let remainder = magnitude % divisor;
+            magnitude /= divisor;
+            if remainder >= divisor - remainder {
+                magnitude += 1;
+            }
+        }
+        digits = precision;
     }
-    let val = val * 10.0_f64.powi(precision as i32);
-    if val > max_val as f64 {
-        return None;
+    let mut buf = magnitude.to_string();
+    let frac_digits = digits.min(frac) as usize;
+    if frac_digits > 0 {
+        if buf.len() <= frac_digits {
+            buf.insert_str(0, &"0".repeat(frac_digits + 1 - buf.len()));
+        }
+        buf.insert(buf.len() - frac_digits, '.');
     }
-    if val.fract() == 0.0 {
-        Some(val as i128)
-    } else {
-        let val = match rounding {
-            FloatRounding::Floor => val.floor(),
-            FloatRounding::Ceil => val.ceil(),
-            FloatRounding::Round => val.round(),
-            FloatRounding::Trunc => val.trunc(),
-        };
-        Some(val as i128)
+    if digits > frac {
+        if frac == 0 {
+            buf.push('.');
+        }
+        buf.push_str(&"0".repeat((digits - frac) as usize));
     }
+    f.pad_integral(value >= 0 || magnitude == 0, "", &buf)
 }
 
-pub fn round_value_up(max_val: u128, precision: u8, val: f64) -> Option<i128> {
-    if !val.is_finite() {
-        return None;
-    }
-    let val = val * 10.0_f64.powi(precision as i32).ceil();
-    if val > max_val as f64 {
-        return None;
-    }
-    Some(val as i128)
+macro_rules! buffer_to_int {
+    ($fn_name:tt, $int_type:ty, $len:tt) => {
+        #[inline]
+        pub fn $fn_name<const LEN: usize>(val: [u8; LEN]) -> $int_type {
+            let z: $int_type;
+            unsafe {
+                let val: [u8; $len] = std::mem::transmute_copy(&val);
+                z = <$int_type>::from_le_bytes(val);
+            }
+            z
+        }
+    };
 }
 
-pub fn round_value_down(max_val: u128, precision: u8, val: f64) -> Option<i128> {
-    if !val.is_finite() {
-        return None;
-    }
-    let val = val * 10.0_f64.powi(precision as i32).floor();
-    if val > max_val as f64 {
-        return None;
-    }
-    Some(val as i128)
-}
+buffer_to_int!(buffer_to_int_32, i32, 4);
+buffer_to_int!(buffer_to_int_64, i64, 8);
+buffer_to_int!(buffer_to_int_128, i128, 16);
 
 macro_rules! add_buffers {
     ($fn_name:tt, $int_type:ty, $len:tt) => {
         #[inline]
-        pub fn $fn_name<'a, const LEN: usize>(le: [u8; LEN], ri: [u8; LEN]) -> Option<[u8; LEN]> {
+        pub fn $fn_name<const LEN: usize>(le: [u8; LEN], ri: [u8; LEN]) -> Option<[u8; LEN]> {
             let z: $int_type;
             unsafe {
                 let le: [u8; $len] = std::mem::transmute_copy(&le);
@@ -75,26 +83,81 @@ add_buffers!(add_buffers_32, i32, 4);
 add_buffers!(add_buffers_64, i64, 8);
 add_buffers!(add_buffers_128, i128, 16);
 
-macro_rules! buffer_is_less_or_equal {
+macro_rules! sub_buffers {
+    ($fn_name:tt, $int_type:ty, $len:tt) => {
+        #[inline]
+        pub fn $fn_name<const LEN: usize>(le: [u8; LEN], ri: [u8; LEN]) -> Option<[u8; LEN]> {
+            let z: $int_type;
+            unsafe {
+                let le: [u8; $len] = std::mem::transmute_copy(&le);
+                z = <$int_type>::from_le_bytes(le);
+            }
+            let y: $int_type;
+            unsafe {
+                let ri: [u8; $len] = std::mem::transmute_copy(&ri);
+                y = <$int_type>::from_le_bytes(ri);
+            }
+            let sub = z.checked_sub(y)?;
+            let sub_: [u8; LEN];
+            unsafe { sub_ = std::mem::transmute_copy(&sub) }
+            Some(sub_)
+        }
+    };
+}
+
+sub_buffers!(sub_buffers_32, i32, 4);
+sub_buffers!(sub_buffers_64, i64, 8);
+sub_buffers!(sub_buffers_128, i128, 16);
+
+macro_rules! neg_buffer {
+    ($fn_name:tt, $int_type:ty, $len:tt) => {
+        #[inline]
+        pub fn $fn_name<const LEN: usize>(val: [u8; LEN]) -> Option<[u8; LEN]> {
+            let z: $int_type;
+            unsafe {
+                let val: [u8; $len] = std::mem::transmute_copy(&val);
+                z = <$int_type>::from_le_bytes(val);
+            }
+            let neg = z.checked_neg()?;
+            let neg_: [u8; LEN];
+            unsafe { neg_ = std::mem::transmute_copy(&neg) }
+            Some(neg_)
+        }
+    };
+}
+
+neg_buffer!(neg_buffer_32, i32, 4);
+neg_buffer!(neg_buffer_64, i64, 8);
+neg_buffer!(neg_buffer_128, i128, 16);
+
+// Return `Less` if the value is below `min`, `Greater` if it is above `max` and `Equal` otherwise
+macro_rules! buffer_is_in_range {
     ($fn_name:tt, $int_type:ty, $len:tt) => {
         #[inline]
-        pub fn $fn_name<const LEN: usize>(val: [u8; LEN], max: $int_type) -> Option<()> {
+        pub fn $fn_name<const LEN: usize>(
+            val: [u8; LEN],
+            min: $int_type,
+            max: $int_type,
+        ) -> std::cmp::Ordering {
             let z: $int_type;
             unsafe {
                 let val: [u8; $len] = std::mem::transmute_copy(&val);
                 z = <$int_type>::from_le_bytes(val);
             }
-            if z.checked_abs()? <= max {
-                return Some(());
+            if z < min {
+                std::cmp::Ordering::Less
+            } else if z > max {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
             }
-            None
         }
     };
 }
 
-buffer_is_less_or_equal!(buffer_is_less_or_equal_32, i32, 4);
-buffer_is_less_or_equal!(buffer_is_less_or_equal_64, i64, 8);
-buffer_is_less_or_equal!(buffer_is_less_or_equal_128, i128, 16);
+buffer_is_in_range!(buffer_is_in_range_32, i32, 4);
+buffer_is_in_range!(buffer_is_in_range_64, i64, 8);
+buffer_is_in_range!(buffer_is_in_range_128, i128, 16);
 
 macro_rules! ord {
     ($fn_name:tt, $int_type:ty, $len:tt) => {
@@ -157,7 +220,7 @@ pub fn buffer_is_negative<const LEN: usize>(val: [u8; LEN]) -> bool {
 macro_rules! mul_buffer {
     ($fn_name:tt, $int_type:ty, $len:tt) => {
         #[inline]
-        pub fn $fn_name<'a, const LEN: usize>(le: [u8; LEN], ri: $int_type) -> Option<[u8; LEN]> {
+        pub fn $fn_name<const LEN: usize>(le: [u8; LEN], ri: $int_type) -> Option<[u8; LEN]> {
             let z: $int_type;
             unsafe {
                 let le: [u8; $len] = std::mem::transmute_copy(&le);
@@ -178,7 +241,7 @@ mul_buffer!(mul_buffer_128, i128, 16);
 
 macro_rules! buffer_to_parts {
     ($fn_name:tt, $int_type:ty, $len:tt) => {
-        pub fn $fn_name<'a, const LEN: usize, const POW: u128>(
+        pub fn $fn_name<const LEN: usize, const POW: u128>(
             buf: [u8; LEN],
         ) -> ($int_type, $int_type, u128) {
             let z: $int_type;
@@ -199,7 +262,7 @@ buffer_to_parts!(buffer_to_parts_128, i128, 16);
 macro_rules! div_buffer {
     ($fn_name:ident, $int_type:ty, $len:tt) => {
         #[inline]
-        pub fn $fn_name<'a, const LEN: usize>(lhs: [u8; LEN], rhs: $int_type) -> Option<[u8; LEN]> {
+        pub fn $fn_name<const LEN: usize>(lhs: [u8; LEN], rhs: $int_type) -> Option<[u8; LEN]> {
             let z: $int_type;
             unsafe {
                 let lhs: [u8; $len] = std::mem::transmute_copy(&lhs);
@@ -219,3 +282,30 @@ macro_rules! div_buffer {
 div_buffer!(div_buffer_32, i32, 4);
 div_buffer!(div_buffer_64, i64, 8);
 div_buffer!(div_buffer_128, i128, 16);
+
+// Multiply or divide a value stored in 4 or 8 bytes for an i128 that does not fit in the storage:
+// the result is computed exactly in an i128 and fails if it does not fit back in the storage
+macro_rules! wide_op_buffer {
+    ($fn_name:ident, $op:ident, $int_type:ty, $len:tt) => {
+        #[inline]
+        pub fn $fn_name<const LEN: usize>(lhs: [u8; LEN], rhs: i128) -> Option<[u8; LEN]> {
+            let z: $int_type;
+            unsafe {
+                let lhs: [u8; $len] = std::mem::transmute_copy(&lhs);
+                z = <$int_type>::from_le_bytes(lhs);
+            }
+            let result = (z as i128).$op(rhs)?;
+            let result = <$int_type>::try_from(result).ok()?.to_le_bytes();
+            let result_: [u8; LEN];
+            unsafe {
+                result_ = std::mem::transmute_copy(&result);
+            }
+            Some(result_)
+        }
+    };
+}
+
+wide_op_buffer!(mul_wide_buffer_32, checked_mul, i32, 4);
+wide_op_buffer!(mul_wide_buffer_64, checked_mul, i64, 8);
+wide_op_buffer!(div_wide_buffer_32, checked_div, i32, 4);
+wide_op_buffer!(div_wide_buffer_64, checked_div, i64, 8);
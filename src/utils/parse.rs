@@ -0,0 +1,163 @@
+use crate::error::Error;
+use std::convert::TryFrom;
+
+const MAX_MAGNITUDE: u128 = 1 << 127;
+
+fn parse_error(position: usize, reason: &'static str) -> Error {
+    Error::Parse { position, reason }
+}
+
+/// Parse a decimal string into a `(mantissa, decimal part)` tuple, the same representation used
+/// by `Asset::try_from((mantissa, decimal_part))`.
+///
+/// Accepted: a leading `+` or `-`, leading zeros, `.5` and `5.`, `_` between digits and an
+/// exponent (`1.5e-3`). Trailing zeros of the decimal part are dropped so `"1.50"` and `"1.5"`
+/// give the same `(15, 1)`.
+///
+/// A malformed string is an `Error::Parse` with the byte offset of the error, a well formed number
+/// that does not fit in an `i128` mantissa is an `Error::Overflow`.
+///
+/// ```
+/// use merx::utils::parse::parse_decimal;
+///
+/// assert_eq!(parse_decimal("-1_000.50"), Ok((-10005, 1)));
+/// assert_eq!(parse_decimal("1.5e-3"), Ok((15, 4)));
+/// assert!(parse_decimal("1.2.3").is_err());
+/// assert_eq!(parse_decimal("1e39"), Err(merx::Error::Overflow));
+/// ```
+pub fn parse_decimal(value: &str) -> Result<(i128, u32), Error> {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() {
+        return Err(parse_error(0, "empty string"));
+    }
+    let mut i = 0;
+    let negative = match bytes[0] {
+        b'-' => {
+            i += 1;
+            true
+        }
+        b'+' => {
+            i += 1;
+            false
+        }
+        _ => false,
+    };
+
+    let mut mantissa: u128 = 0;
+    // Zeros not yet multiplied into the mantissa, so that `0.1000` or `1000e-3` never overflow.
+    let mut pending_zeros: u32 = 0;
+    let mut scale: u32 = 0;
+    let mut digits = 0;
+    let mut in_fraction = false;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'0'..=b'9' => {
+                let digit = (bytes[i] - b'0') as u128;
+                if in_fraction {
+                    scale = scale.saturating_add(1);
+                }
+                if digit == 0 {
+                    pending_zeros += 1;
+                } else {
+                    mantissa = checked_mul_pow_10(mantissa, pending_zeros + 1)
+                        .and_then(|m| m.checked_add(digit))
+                        .filter(|m| *m <= MAX_MAGNITUDE)
+                        .ok_or(Error::Overflow)?;
+                    pending_zeros = 0;
+                }
+                digits += 1;
+            }
+            b'_' => {
+                let follows_digit =
+                    i > 0 && (bytes[i - 1].is_ascii_digit() || bytes[i - 1] == b'_');
+                let precedes_digit = bytes
+                    .get(i + 1)
+                    .map(|b| b.is_ascii_digit() || *b == b'_')
+                    .unwrap_or(false);
+                if !follows_digit || !precedes_digit {
+                    return Err(parse_error(i, "`_` must be between digits"));
+                }
+            }
+            b'.' => {
+                if in_fraction {
+                    return Err(parse_error(i, "more than one decimal point"));
+                }
+                in_fraction = true;
+            }
+            b'e' | b'E' => break,
+            _ => return Err(parse_error(i, "invalid digit")),
+        }
+        i += 1;
+    }
+    if digits == 0 {
+        return Err(parse_error(i, "no digits"));
+    }
+
+    let mut exponent: i64 = 0;
+    let exponent_start = i;
+    if i < bytes.len() {
+        i += 1;
+        let exponent_negative = match bytes.get(i) {
+            Some(b'-') => {
+                i += 1;
+                true
+            }
+            Some(b'+') => {
+                i += 1;
+                false
+            }
+            _ => false,
+        };
+        if i >= bytes.len() {
+            return Err(parse_error(exponent_start, "missing exponent"));
+        }
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                return Err(parse_error(i, "invalid exponent digit"));
+            }
+            // An exponent above u32::MAX overflows or is below any unit, so it is capped
+            exponent = (exponent * 10 + (bytes[i] - b'0') as i64).min(u32::MAX as i64 + 1);
+            i += 1;
+        }
+        if exponent_negative {
+            exponent = -exponent;
+        }
+    }
+
+    let exponent = exponent + pending_zeros as i64 - scale as i64;
+    let (mantissa, scale) = if mantissa == 0 {
+        (0, 0)
+    } else if exponent >= 0 {
+        let mantissa = u32::try_from(exponent)
+            .ok()
+            .and_then(|exponent| checked_mul_pow_10(mantissa, exponent))
+            .filter(|m| *m <= MAX_MAGNITUDE)
+            .ok_or(Error::Overflow)?;
+        (mantissa, 0)
+    } else {
+        // A value with more than u32::MAX decimal digits is below any unit, so the scale is
+        // capped: it is rounded like the exact value
+        let scale = u32::try_from(-exponent).unwrap_or(u32::MAX);
+        (mantissa, scale)
+    };
+
+    let mantissa = if !negative {
+        if mantissa == MAX_MAGNITUDE {
+            return Err(Error::Overflow);
+        }
+        mantissa as i128
+    } else if mantissa == MAX_MAGNITUDE {
+        i128::MIN
+    } else {
+        -(mantissa as i128)
+    };
+    Ok((mantissa, scale))
+}
+
+fn checked_mul_pow_10(value: u128, exp: u32) -> Option<u128> {
+    if value == 0 {
+        return Some(0);
+    }
+    value.checked_mul(10_u128.checked_pow(exp)?)
+}
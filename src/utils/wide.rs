@@ -0,0 +1,339 @@
+use crate::error::Error;
+use crate::rounding::{Fraction, RoundingMode};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+/// An unsigned 256 bit integer, used as intermediate value when the product of two i128 must be
+/// divided or rounded without overflowing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct U256 {
+    pub hi: u128,
+    pub lo: u128,
+}
+
+const LO_MASK: u128 = u64::MAX as u128;
+
+impl U256 {
+    pub const ZERO: U256 = U256 { hi: 0, lo: 0 };
+
+    #[inline]
+    pub fn from_u128(value: u128) -> Self {
+        U256 { hi: 0, lo: value }
+    }
+
+    /// Full product of two u128
+    #[inline]
+    pub fn mul_u128(lhs: u128, rhs: u128) -> Self {
+        let (l1, l0) = (lhs >> 64, lhs & LO_MASK);
+        let (r1, r0) = (rhs >> 64, rhs & LO_MASK);
+        let p00 = l0 * r0;
+        let p01 = l0 * r1;
+        let p10 = l1 * r0;
+        let p11 = l1 * r1;
+        let middle = (p00 >> 64) + (p01 & LO_MASK) + (p10 & LO_MASK);
+        let lo = (p00 & LO_MASK) | (middle << 64);
+        let hi = p11 + (p01 >> 64) + (p10 >> 64) + (middle >> 64);
+        U256 { hi, lo }
+    }
+
+    #[inline]
+    pub fn checked_mul_u128(self, rhs: u128) -> Option<Self> {
+        let lo = U256::mul_u128(self.lo, rhs);
+        let hi = U256::mul_u128(self.hi, rhs);
+        if hi.hi != 0 {
+            return None;
+        }
+        Some(U256 {
+            hi: hi.lo.checked_add(lo.hi)?,
+            lo: lo.lo,
+        })
+    }
+
+    #[inline]
+    pub fn to_u128(self) -> Option<u128> {
+        if self.hi == 0 {
+            Some(self.lo)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn shl1(self) -> Self {
+        U256 {
+            hi: (self.hi << 1) | (self.lo >> 127),
+            lo: self.lo << 1,
+        }
+    }
+
+    /// Quotient and remainder of the division by `divisor`, `divisor` must not be 0
+    pub fn div_rem_u128(self, divisor: u128) -> (Self, u128) {
+        debug_assert!(divisor != 0);
+        if self.hi == 0 {
+            return (U256::from_u128(self.lo / divisor), self.lo % divisor);
+        }
+        let mut quotient = U256::ZERO;
+        let mut remainder: u128 = 0;
+        for i in (0..256).rev() {
+            let bit = if i >= 128 {
+                (self.hi >> (i - 128)) & 1
+            } else {
+                (self.lo >> i) & 1
+            };
+            // remainder < divisor so the shifted value fits in 129 bits
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | bit;
+            quotient = quotient.shl1();
+            if carry == 1 || remainder >= divisor {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient.lo |= 1;
+            }
+        }
+        (quotient, remainder)
+    }
+
+    #[inline]
+    fn leading_zeros(self) -> u32 {
+        if self.hi != 0 {
+            self.hi.leading_zeros()
+        } else {
+            128 + self.lo.leading_zeros()
+        }
+    }
+
+    /// `self * 2^exp`, None if it does not fit in 256 bits
+    pub fn checked_shl(self, exp: u32) -> Option<Self> {
+        if self == U256::ZERO || exp == 0 {
+            return Some(self);
+        }
+        if exp > self.leading_zeros() {
+            return None;
+        }
+        Some(if exp >= 128 {
+            U256 {
+                hi: self.lo << (exp - 128),
+                lo: 0,
+            }
+        } else {
+            U256 {
+                hi: (self.hi << exp) | (self.lo >> (128 - exp)),
+                lo: self.lo << exp,
+            }
+        })
+    }
+
+    /// True if any of the `count` least significant bits is set
+    #[inline]
+    fn low_bits_are_set(self, count: u32) -> bool {
+        match count {
+            0 => false,
+            1..=127 => self.lo & ((1 << count) - 1) != 0,
+            128 => self.lo != 0,
+            129..=255 => self.lo != 0 || self.hi & ((1 << (count - 128)) - 1) != 0,
+            _ => self != U256::ZERO,
+        }
+    }
+
+    #[inline]
+    fn bit(self, index: u32) -> bool {
+        match index {
+            0..=127 => (self.lo >> index) & 1 == 1,
+            128..=255 => (self.hi >> (index - 128)) & 1 == 1,
+            _ => false,
+        }
+    }
+
+    /// Quotient of the division by 2^exp and the discarded fraction
+    pub(crate) fn div_pow_2(self, exp: u32) -> (Self, Fraction) {
+        if exp == 0 {
+            return (self, Fraction::Zero);
+        }
+        let quotient = match exp {
+            1..=127 => U256 {
+                hi: self.hi >> exp,
+                lo: (self.lo >> exp) | (self.hi << (128 - exp)),
+            },
+            128..=255 => U256::from_u128(self.hi >> (exp - 128)),
+            _ => U256::ZERO,
+        };
+        let fraction = match (self.bit(exp - 1), self.low_bits_are_set(exp - 1)) {
+            (false, false) => Fraction::Zero,
+            (false, true) => Fraction::BelowHalf,
+            (true, false) => Fraction::Half,
+            (true, true) => Fraction::AboveHalf,
+        };
+        (quotient, fraction)
+    }
+
+    /// Quotient of the division by 10^exp and the discarded fraction
+    pub(crate) fn div_pow_10(self, exp: u32) -> (Self, Fraction) {
+        if exp == 0 {
+            return (self, Fraction::Zero);
+        }
+        // Divide by 10^(exp - 1) keeping track of any non zero digit then look at the last digit
+        let mut quotient = self;
+        let mut sticky = false;
+        let mut left = exp - 1;
+        while left > 0 {
+            if quotient == U256::ZERO {
+                break;
+            }
+            let step = left.min(38);
+            let (q, r) = quotient.div_rem_u128(10_u128.pow(step));
+            quotient = q;
+            sticky |= r != 0;
+            left -= step;
+        }
+        let (quotient, digit) = quotient.div_rem_u128(10);
+        let fraction = match (digit.cmp(&5), sticky) {
+            (Ordering::Equal, false) => Fraction::Half,
+            (Ordering::Equal, true) | (Ordering::Greater, _) => Fraction::AboveHalf,
+            (Ordering::Less, _) if digit == 0 && !sticky => Fraction::Zero,
+            (Ordering::Less, _) => Fraction::BelowHalf,
+        };
+        (quotient, fraction)
+    }
+}
+
+/// Apply the sign to a magnitude, fail if the result does not fit in an i128
+#[inline]
+pub fn to_signed(magnitude: u128, negative: bool) -> Result<i128, Error> {
+    const MIN_MAGNITUDE: u128 = 1 << 127;
+    if negative {
+        match magnitude.cmp(&MIN_MAGNITUDE) {
+            Ordering::Less => Ok(-(magnitude as i128)),
+            Ordering::Equal => Ok(i128::MIN),
+            Ordering::Greater => Err(Error::Overflow),
+        }
+    } else if magnitude < MIN_MAGNITUDE {
+        Ok(magnitude as i128)
+    } else {
+        Err(Error::Overflow)
+    }
+}
+
+/// Round a truncated quotient to a multiple of `tick` and apply the sign
+#[inline]
+pub(crate) fn round_quotient(
+    quotient: U256,
+    negative: bool,
+    fraction: Fraction,
+    rounding: RoundingMode,
+    tick: u128,
+) -> Result<i128, Error> {
+    let (quotient, fraction) = if tick == 1 {
+        (quotient, fraction)
+    } else {
+        let (quotient, remainder) = quotient.div_rem_u128(tick);
+        (quotient, fraction.carry(remainder, tick))
+    };
+    let mut magnitude = quotient.to_u128().ok_or(Error::Overflow)?;
+    if rounding.increments(negative, magnitude & 1 == 1, fraction)? {
+        magnitude = magnitude.checked_add(1).ok_or(Error::Overflow)?;
+    }
+    let magnitude = magnitude.checked_mul(tick).ok_or(Error::Overflow)?;
+    to_signed(magnitude, negative)
+}
+
+/// `value * 10^exp` rounded with `rounding` to a multiple of `tick`, exp can be negative
+pub fn mul_pow_10_rounded(
+    value: U256,
+    negative: bool,
+    exp: i64,
+    rounding: RoundingMode,
+    tick: u128,
+) -> Result<i128, Error> {
+    if value == U256::ZERO {
+        return Ok(0);
+    }
+    if exp >= 0 {
+        let pow = u32::try_from(exp)
+            .ok()
+            .and_then(|exp| 10_u128.checked_pow(exp))
+            .ok_or(Error::Overflow)?;
+        let value = value.checked_mul_u128(pow).ok_or(Error::Overflow)?;
+        round_quotient(value, negative, Fraction::Zero, rounding, tick)
+    } else {
+        let exp = (-exp).min(u32::MAX as i64) as u32;
+        let (quotient, fraction) = value.div_pow_10(exp);
+        round_quotient(quotient, negative, fraction, rounding, tick)
+    }
+}
+
+/// `value * mantissa * 10^-scale` rounded with `rounding` to a multiple of `tick`
+pub fn mul_decimal_rounded(
+    value: i128,
+    factor: (i128, u32),
+    rounding: RoundingMode,
+    tick: u128,
+) -> Result<i128, Error> {
+    let (mantissa, scale) = factor;
+    let negative = (value < 0) != (mantissa < 0);
+    let product = U256::mul_u128(
+        value.wrapping_abs() as u128,
+        mantissa.wrapping_abs() as u128,
+    );
+    mul_pow_10_rounded(product, negative, -(scale as i64), rounding, tick)
+}
+
+/// `value / (mantissa * 10^-scale)` rounded with `rounding` to a multiple of `tick`
+pub fn div_decimal_rounded(
+    value: i128,
+    factor: (i128, u32),
+    rounding: RoundingMode,
+    tick: u128,
+) -> Result<i128, Error> {
+    let (mut mantissa, mut scale) = factor;
+    if mantissa == 0 {
+        return Err(Error::DivisionByZero);
+    }
+    if value == 0 {
+        return Ok(0);
+    }
+    while scale > 0 && mantissa % 10 == 0 {
+        mantissa /= 10;
+        scale -= 1;
+    }
+    let negative = (value < 0) != (mantissa < 0);
+    let divisor = mantissa.wrapping_abs() as u128;
+    // value * 10^scale, if it does not fit in 256 bits the quotient does not fit in 128 bits
+    let mut dividend = U256::from_u128(value.wrapping_abs() as u128);
+    let mut left = scale;
+    while left > 0 {
+        let step = left.min(38);
+        dividend = dividend
+            .checked_mul_u128(10_u128.pow(step))
+            .ok_or(Error::Overflow)?;
+        left -= step;
+    }
+    let (quotient, remainder) = dividend.div_rem_u128(divisor);
+    let fraction = Fraction::from_remainder(remainder, divisor);
+    round_quotient(quotient, negative, fraction, rounding, tick)
+}
+
+/// `value * num / den` rounded with `rounding` to a multiple of `tick`. The product is computed
+/// in 128 bits when both factors fit in 64 bits, like the values stored in 4 or 8 bytes with a
+/// small factor, and in 256 bits otherwise, so it never overflows before the division.
+pub fn mul_div_rounded(
+    value: i128,
+    num: i128,
+    den: i128,
+    rounding: RoundingMode,
+    tick: u128,
+) -> Result<i128, Error> {
+    if den == 0 {
+        return Err(Error::DivisionByZero);
+    }
+    let negative = ((value < 0) != (num < 0)) != (den < 0);
+    let value = value.wrapping_abs() as u128;
+    let num = num.wrapping_abs() as u128;
+    let divisor = den.wrapping_abs() as u128;
+    let (quotient, remainder) = if value <= LO_MASK && num <= LO_MASK {
+        let product = value * num;
+        (U256::from_u128(product / divisor), product % divisor)
+    } else {
+        U256::mul_u128(value, num).div_rem_u128(divisor)
+    };
+    let fraction = Fraction::from_remainder(remainder, divisor);
+    round_quotient(quotient, negative, fraction, rounding, tick)
+}